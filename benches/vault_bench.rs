@@ -0,0 +1,88 @@
+//! Débit du format de coffre: dérivation de clé, chiffrement et aller-retour complet.
+//!
+//! `cargo bench --bench vault_bench`. Argon2 tourne avec des paramètres réduits pour que
+//! les mesures portent sur le format (sérialisation, AES-GCM, base64) et non sur le KDF.
+//! Quand le format gagnera des options (algorithme, compression), les ajouter à `VARIANTS`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use mdp_manager::crypto::{decrypt, derive_key, encrypt, generate_nonce, generate_salt, CryptoParams};
+use mdp_manager::models::{Entry, Vault};
+use mdp_manager::storage::{create_vault_in, load_vault_from, save_vault_session_to};
+use mdp_manager::vault_storage::MemoryStorage;
+
+const PASSWORD: &str = "benchmark_password";
+
+/// Tailles de coffre mesurées (nombre d'entrées)
+const VARIANTS: &[usize] = &[10, 500];
+
+fn bench_params() -> CryptoParams {
+    CryptoParams {
+        time_cost: 1,
+        memory_cost: 1024,
+        parallelism: 1,
+    }
+}
+
+/// Coffre représentatif: identifiants, URL, notes et tags remplis
+fn sample_vault(entries: usize) -> Vault {
+    let mut vault = Vault::new();
+    for i in 0..entries {
+        let mut entry = Entry::new(format!("Site {}", i), format!("user{}@example.com", i), format!("P@ss-{:08}-word", i));
+        entry.urls = vec![format!("https://site{}.example.com/login", i)];
+        entry.notes = Some("Question secrète: nom du premier animal de compagnie".to_string());
+        entry.tags = vec!["perso".to_string(), format!("groupe{}", i % 7)];
+        vault.add_entry(entry);
+    }
+    vault
+}
+
+fn bench_derive_key(c: &mut Criterion) {
+    let salt = generate_salt();
+    let params = bench_params();
+    c.bench_function("derive_key", |b| {
+        b.iter(|| derive_key(black_box(PASSWORD), &salt, &params).unwrap())
+    });
+}
+
+fn bench_cipher(c: &mut Criterion) {
+    let key = derive_key(PASSWORD, &generate_salt(), &bench_params()).unwrap();
+    let nonce = generate_nonce();
+    let mut group = c.benchmark_group("cipher");
+
+    for &size in VARIANTS {
+        let plaintext = serde_json::to_vec(&sample_vault(size)).unwrap();
+        let ciphertext = encrypt(&plaintext, &key, &nonce).unwrap();
+        group.throughput(Throughput::Bytes(plaintext.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("encrypt", size), &plaintext, |b, data| {
+            b.iter(|| encrypt(black_box(data), &key, &nonce).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("decrypt", size), &ciphertext, |b, data| {
+            b.iter(|| decrypt(black_box(data), &key, &nonce).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_roundtrip(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vault");
+
+    for &size in VARIANTS {
+        let vault = sample_vault(size);
+        let storage = MemoryStorage::new();
+        let session = create_vault_in(&vault, &storage, PASSWORD, bench_params()).unwrap();
+
+        // Enregistrement avec la clé de session: pas de dérivation
+        group.bench_with_input(BenchmarkId::new("save", size), &vault, |b, vault| {
+            b.iter(|| save_vault_session_to(black_box(vault), &storage, &session).unwrap())
+        });
+        // Ouverture complète, dérivation comprise
+        group.bench_with_input(BenchmarkId::new("load", size), &storage, |b, storage| {
+            b.iter(|| load_vault_from(black_box(storage), PASSWORD).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_derive_key, bench_cipher, bench_roundtrip);
+criterion_main!(benches);
@@ -1,54 +1,226 @@
-use crate::models::{AuditAction, AuditEntry, Entry, Vault};
-use crate::password_generator::*;
-use crate::storage::*;
-use arboard::Clipboard;
+#[cfg(feature = "global-hotkey")]
+use crate::lock_hotkey::LockHotkey;
+use crate::panic_guard::{self, SharedSession};
+use mdp_manager::clipboard::{ClearOutcome, ClipboardManager};
+use mdp_manager::crypto::{combine_key, derive_key, detect_kdf_params, generate_salt, CryptoParams, MasterFingerprint};
+#[cfg(feature = "deep-link")]
+use mdp_manager::deep_link::DeepLink;
+use mdp_manager::models::{
+    clickable_url, format_entry, AuditAction, AuditEntry, Entry, EntryKind, MatchField, MatchInfo,
+    PasswordAge, TemplateEscape, Vault, VaultMeta, DEFAULT_LOGIN_LABEL, VAULT_FILE_VERSION,
+};
+use mdp_manager::password_generator::*;
+use mdp_manager::settings::{default_vault_path, CopyTemplate, Settings, TimeDisplay};
+use mdp_manager::storage::*;
+use mdp_manager::totp::parse_totp;
+use mdp_manager::vault_storage::resolve_vault_path;
 use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
 use std::time::{Duration, Instant};
 use uuid::Uuid;
+use zeroize::Zeroize;
+
+/// Titre de la fenêtre, suivi de " *" tant que des modifications ne sont pas enregistrées
+pub const APP_TITLE: &str = "Gestionnaire de Mots de Passe";
+
+/// Délai de regroupement des écritures sur disque après une modification
+const AUTOSAVE_DELAY_SECS: u64 = 3;
+
+/// Info-bulle des boutons de copie lorsque arboard n'a pas pu s'initialiser
+const CLIPBOARD_UNAVAILABLE: &str = "Presse-papiers indisponible sur ce système";
+
+const ENTRY_ICONS: &[&str] = &[
+    "🔐", "🌐", "📧", "🏦", "💳", "🛒", "🎮", "💼", "🏠", "📱", "☁️", "🔧",
+];
 
 pub struct PasswordManagerApp {
     // État du coffre
     vault: Option<Vault>,
     vault_path: Option<PathBuf>,
     is_locked: bool,
+    pending_load: Option<PendingLoad>,
+    vault_slot: VaultSlot,
+    master_fingerprint: Option<MasterFingerprint>,
+    session: SharedSession,
+    /// Paramètres KDF des nouveaux coffres, adaptés à la mémoire disponible
+    kdf_params: CryptoParams,
+    // Assistant de premier lancement
+    setup_step: Option<SetupStep>,
+    setup_confirm_password: String,
+    setup_kdf_ms: Option<u128>,
+    setup_import_path: String,
+    /// Durée de dérivation de clé mesurée au dernier déverrouillage
+    last_derive_ms: Option<u128>,
+    // Sauvegarde différée
+    dirty_since: Option<Instant>,
+    /// La dernière écriture a échoué: les modifications n'existent qu'en mémoire
+    save_failed: bool,
+    /// Modifications non enregistrées au verrouillage, chiffrées avec la clé de session
+    unsaved_snapshot: Option<SealedSnapshot>,
+    /// État « modifié » reflété dans le titre de la fenêtre lors de la dernière mise à jour
+    title_dirty: bool,
+    master_reused_by: Vec<Uuid>,
+    /// Aperçu en clair affiché sur l'écran de verrouillage, si le coffre le publie
+    unlock_preview: Option<VaultMeta>,
 
     // UI État
     screen: Screen,
     master_password: String,
+    caps_lock_on: bool,
     new_vault_path: String,
     error_message: Option<String>,
-    success_message: Option<String>,
+    toasts: Vec<Toast>,
 
     // Entrées
     selected_entry: Option<Uuid>,
+    detail_filter: String,
+    detail_scroll_pending: bool,
     search_query: String,
     filtered_entries: Vec<Uuid>,
+    /// La sélection vient du clavier: faire défiler la liste jusqu'à elle
+    scroll_to_selected: bool,
+    sort_order: SortOrder,
+    show_sections: bool,
+    /// Vue tableau à la place du détail d'une entrée
+    table_view: bool,
+    table_sort: TableColumn,
+    table_sort_ascending: bool,
 
     // Modal
     show_entry_modal: bool,
     editing_entry: Option<Entry>,
 
+    totp_input: String,
+    /// Tag en cours de saisie dans l'éditeur
+    tag_input: String,
+    /// Mot de passe affiché en clair dans l'éditeur (choix conservé jusqu'au verrouillage)
+    editor_show_password: bool,
+    /// Avertissement affiché quand le nom saisi est déjà pris par une autre entrée
+    duplicate_name_warning: bool,
+
     // Générateur
     show_generator: bool,
     generator_options: PasswordGeneratorOptions,
     generated_password: String,
+    show_generator_advanced: bool,
+    preset_name: String,
+    selected_preset: Option<usize>,
+
+    // Palette de commandes
+    show_palette: bool,
+    palette_query: String,
+    palette_selected: usize,
+
+    // Création multiple
+    show_batch_create: bool,
+    batch_count: usize,
+    batch_drafts: Vec<Entry>,
+
+    // Doublons exacts
+    show_duplicates: bool,
+    duplicate_groups: Vec<Vec<Uuid>>,
+    duplicate_keep: Vec<Uuid>,
 
     // Confirmations
     confirm_delete: Option<Uuid>,
-    confirm_export_plain: bool,
+    confirm_export_plain: Option<PlainExport>,
+    confirm_overwrite: bool,
+    confirm_replace_import: bool,
+    /// Mot à retaper pour confirmer le remplacement du coffre
+    replace_confirm_text: String,
+
+    // Sauvegarde chiffrée
+    show_backup: bool,
+    backup_path: String,
+    backup_password: String,
+
+    // Export CSV
+    show_csv_export: bool,
+    csv_export_path: String,
+    csv_export_options: CsvExportOptions,
+    csv_export_encrypt: bool,
+    csv_export_password: String,
+
+    // Export et import JSON
+    json_path: String,
+    json_include_secrets: bool,
+
+    // Import CSV depuis du texte
+    show_csv_paste: bool,
+    csv_paste_text: String,
+
+    // Coffre leurre
+    show_decoy_setup: bool,
+    decoy_password: String,
+
+    // Ré-chiffrement
+    show_reencrypt: bool,
+    reencrypt_password: String,
+
+    // Gestion des dossiers
+    show_folders: bool,
+    /// Dossier en cours de renommage et nouveau nom saisi
+    folder_rename: Option<(String, String)>,
+
+    // Ouverture groupée de sites
+    show_batch_open: bool,
+    batch_open_selection: Vec<Uuid>,
+    confirm_batch_open: bool,
+
+    // Enregistrement d'un coffre temporaire
+    show_save_as: bool,
+    save_as_path: String,
+    save_as_password: String,
+
+    // Parts de clé (Shamir)
+    show_key_shares: bool,
+    share_count: u8,
+    share_threshold: u8,
+    key_shares: Vec<String>,
+    show_share_unlock: bool,
+    share_input: String,
 
     // Verrouillage auto
     last_activity: Instant,
-    auto_lock_seconds: u64,
+    #[cfg(feature = "global-hotkey")]
+    lock_hotkey: Option<LockHotkey>,
+    /// Entrée demandée par un lien `mdp://`, sélectionnée dès que le coffre est ouvert
+    #[cfg(feature = "deep-link")]
+    pending_deep_link: Option<Uuid>,
 
     // Audit
     audit_log: Vec<AuditEntry>,
     show_audit: bool,
 
+    // Santé du coffre
+    show_health: bool,
+    /// Répartition par force, calculée à l'ouverture de la fenêtre
+    strength_histogram: Option<[usize; 4]>,
+    /// Liste locale de hachés compromis (format « Pwned Passwords »)
+    breach_list_path: String,
+    /// Résumé affiché une fois après l'ouverture, jusqu'à sa fermeture
+    health_banner: Option<String>,
+
+    // Préférences
+    settings: Settings,
+    show_settings: bool,
+    config_path: String,
+    /// Reprendre aussi le raccourci de verrouillage et les liens `mdp://` d'une configuration importée
+    config_import_security: bool,
+
     // Clipboard
-    clipboard: Option<Clipboard>,
-    clipboard_clear_time: Option<Instant>,
+    clipboard: ClipboardManager,
     clipboard_clear_delay: u64,
+    show_clipboard_warning: bool,
+    last_copied: Option<LastCopied>,
+    revealed_password: Option<Uuid>,
+    /// Second mot de passe saisi pour protéger ou révéler des notes
+    sealed_notes_password: String,
+    /// Notes protégées déchiffrées, pour l'entrée indiquée
+    revealed_notes: Option<(Uuid, String)>,
+    /// Échéance de l'affichage global des mots de passe ("tout afficher")
+    reveal_all_until: Option<Instant>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -58,72 +230,565 @@ enum Screen {
     Unlock,
 }
 
+/// Étapes de l'assistant de premier lancement
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SetupStep {
+    Location,
+    Kdf,
+    Password,
+    Import,
+}
+
+impl SetupStep {
+    const ALL: [SetupStep; 4] = [SetupStep::Location, SetupStep::Kdf, SetupStep::Password, SetupStep::Import];
+
+    fn label(&self) -> &str {
+        match self {
+            SetupStep::Location => "Emplacement du coffre",
+            SetupStep::Kdf => "Réglage de la dérivation de clé",
+            SetupStep::Password => "Mot de passe maître",
+            SetupStep::Import => "Import (facultatif)",
+        }
+    }
+
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|s| s == self).unwrap_or(0)
+    }
+}
+
+/// Notification non bloquante affichée dans un coin puis retirée automatiquement
+struct Toast {
+    message: String,
+    warning: bool,
+    created_at: Instant,
+}
+
+/// Rafraîchissement des comptes à rebours affichés (code TOTP, « Tout masquer »)
+const COUNTDOWN_REPAINT: Duration = Duration::from_secs(1);
+
+/// Scrutation du raccourci global, dont les événements n'arrivent pas par egui
+#[cfg(feature = "global-hotkey")]
+const HOTKEY_POLL: Duration = Duration::from_millis(250);
+
+/// Durée d'affichage d'une notification
+const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+/// Durée pendant laquelle le dernier secret copié peut être recopié
+const LAST_COPIED_MAX_AGE: Duration = Duration::from_secs(300);
+
+/// Dernier secret copié, pour le recopier après un effacement trop rapide
+struct LastCopied {
+    text: String,
+    delay_override: Option<u64>,
+    copied_at: Instant,
+}
+
+impl Drop for LastCopied {
+    fn drop(&mut self) {
+        self.text.zeroize();
+    }
+}
+
+/// Chargement du coffre en cours sur un thread séparé (dérivation Argon2)
+struct PendingLoad {
+    receiver: Receiver<LoadMessage>,
+    path: PathBuf,
+    kind: LoadKind,
+    stage: LoadStage,
+}
+
+/// Messages du fil de chargement: progression puis résultat
+enum LoadMessage {
+    Progress(LoadStage),
+    Done(Result<(LoadedVault, Option<MasterFingerprint>), StorageError>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LoadKind {
+    Open,
+    Unlock,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EntryAction {
+    CopyPassword,
+    CopyLogin,
+    OpenUrl,
+    RotatePassword,
+    Edit,
+    Duplicate,
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SortOrder {
+    Name,
+    RecentlyModified,
+    MostUsed,
+}
+
+/// Export en clair en attente de confirmation
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PlainExport {
+    Csv,
+    Json,
+}
+
+/// Colonne de tri de la vue tableau
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TableColumn {
+    Name,
+    Login,
+}
+
+impl SortOrder {
+    fn label(&self) -> &str {
+        match self {
+            SortOrder::Name => "Nom",
+            SortOrder::RecentlyModified => "Modifié récemment",
+            SortOrder::MostUsed => "Plus utilisées",
+        }
+    }
+}
+
+/// Actions accessibles depuis la palette de commandes (Ctrl+P)
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PaletteAction {
+    Lock,
+    NewEntry,
+    Generate,
+    ExportCsv,
+    Backup,
+    Settings,
+    Audit,
+    Health,
+    MergeDuplicates,
+    RecopyLast,
+    BatchOpen,
+    SelectEntry(Uuid),
+}
+
+const PALETTE_COMMANDS: &[(&str, PaletteAction)] = &[
+    ("🔒 Verrouiller le coffre", PaletteAction::Lock),
+    ("➕ Nouvelle entrée", PaletteAction::NewEntry),
+    ("🎲 Générer un mot de passe", PaletteAction::Generate),
+    ("📄 Export CSV", PaletteAction::ExportCsv),
+    ("💾 Sauvegarde chiffrée", PaletteAction::Backup),
+    ("⚙️ Préférences", PaletteAction::Settings),
+    ("📊 Journal d'audit", PaletteAction::Audit),
+    ("🩺 Santé du coffre", PaletteAction::Health),
+    ("🧹 Fusionner les doublons", PaletteAction::MergeDuplicates),
+    ("📋 Recopier le dernier (Ctrl+Maj+R)", PaletteAction::RecopyLast),
+    ("🌐 Ouvrir plusieurs sites", PaletteAction::BatchOpen),
+];
+
+/// Nombre maximal d'onglets ouverts d'un coup par l'ouverture groupée
+const BATCH_OPEN_MAX: usize = 10;
+
+/// Au-delà d'une heure d'inactivité, le délai de verrouillage est signalé comme risqué
+const AUTO_LOCK_SAFE_MAX_SECS: u64 = 3600;
+
+/// Mot à retaper pour confirmer le remplacement complet du coffre par une sauvegarde
+const REPLACE_CONFIRM_WORD: &str = "REMPLACER";
+
+/// Part d'identifiants faibles à partir de laquelle le bandeau d'alerte s'affiche à l'ouverture
+const WEAK_FRACTION_WARNING: f32 = 0.2;
+
+/// Nombre maximal de résultats affichés dans la palette
+const PALETTE_MAX_RESULTS: usize = 12;
+
+/// Échec de création du leurre, volontairement identique quel que soit l'emplacement ouvert
+const DECOY_CREATE_FAILED: &str = "Impossible de créer le coffre leurre";
+
+/// En dessous, la dérivation de clé est assez rapide pour supporter plus de passes
+const KDF_FAST_MS: u128 = 300;
+
+/// Au-delà, chaque déverrouillage fait attendre plusieurs secondes
+const KDF_SLOW_MS: u128 = 2000;
+
 impl Default for PasswordManagerApp {
     fn default() -> Self {
         Self {
             vault: None,
             vault_path: None,
             is_locked: false,
+            pending_load: None,
+            vault_slot: VaultSlot::Primary,
+            master_fingerprint: None,
+            session: SharedSession::default(),
+            kdf_params: CryptoParams::default(),
+            setup_step: None,
+            setup_confirm_password: String::new(),
+            setup_kdf_ms: None,
+            last_derive_ms: None,
+            setup_import_path: String::new(),
+            dirty_since: None,
+            save_failed: false,
+            unsaved_snapshot: None,
+            title_dirty: false,
+            master_reused_by: Vec::new(),
+            unlock_preview: None,
             screen: Screen::Welcome,
             master_password: String::new(),
+            caps_lock_on: false,
             new_vault_path: String::new(),
             error_message: None,
-            success_message: None,
+            toasts: Vec::new(),
             selected_entry: None,
+            detail_filter: String::new(),
+            detail_scroll_pending: false,
             search_query: String::new(),
             filtered_entries: Vec::new(),
+            scroll_to_selected: false,
+            sort_order: SortOrder::Name,
+            table_view: false,
+            table_sort: TableColumn::Name,
+            table_sort_ascending: true,
+            show_sections: false,
             show_entry_modal: false,
             editing_entry: None,
+            totp_input: String::new(),
+            tag_input: String::new(),
+            editor_show_password: false,
+            duplicate_name_warning: false,
             show_generator: false,
             generator_options: PasswordGeneratorOptions::default(),
             generated_password: String::new(),
+            show_generator_advanced: false,
+            preset_name: String::new(),
+            selected_preset: None,
+            show_palette: false,
+            palette_query: String::new(),
+            palette_selected: 0,
+            show_batch_create: false,
+            batch_count: 5,
+            batch_drafts: Vec::new(),
+            show_duplicates: false,
+            duplicate_groups: Vec::new(),
+            duplicate_keep: Vec::new(),
             confirm_delete: None,
-            confirm_export_plain: false,
+            confirm_export_plain: None,
+            confirm_overwrite: false,
+            confirm_replace_import: false,
+            replace_confirm_text: String::new(),
+            show_backup: false,
+            backup_path: String::new(),
+            backup_password: String::new(),
+            show_csv_export: false,
+            csv_export_path: String::new(),
+            csv_export_encrypt: false,
+            json_path: String::new(),
+            json_include_secrets: false,
+            csv_export_password: String::new(),
+            csv_export_options: CsvExportOptions::default(),
+            show_csv_paste: false,
+            csv_paste_text: String::new(),
+            show_decoy_setup: false,
+            decoy_password: String::new(),
+            show_reencrypt: false,
+            reencrypt_password: String::new(),
+            show_folders: false,
+            folder_rename: None,
+            show_batch_open: false,
+            batch_open_selection: Vec::new(),
+            confirm_batch_open: false,
+            show_save_as: false,
+            save_as_path: String::new(),
+            save_as_password: String::new(),
+            show_key_shares: false,
+            share_count: 5,
+            share_threshold: 3,
+            key_shares: Vec::new(),
+            show_share_unlock: false,
+            share_input: String::new(),
             last_activity: Instant::now(),
-            auto_lock_seconds: 300,
+            #[cfg(feature = "global-hotkey")]
+            lock_hotkey: None,
+            #[cfg(feature = "deep-link")]
+            pending_deep_link: None,
             audit_log: Vec::new(),
             show_audit: false,
-            clipboard: Clipboard::new().ok(),
-            clipboard_clear_time: None,
+            show_health: false,
+            strength_histogram: None,
+            breach_list_path: String::new(),
+            health_banner: None,
+            settings: Settings::load(),
+            show_settings: false,
+            config_path: String::new(),
+            config_import_security: false,
+            clipboard: ClipboardManager::system(),
             clipboard_clear_delay: 30,
+            show_clipboard_warning: false,
+            last_copied: None,
+            revealed_password: None,
+            sealed_notes_password: String::new(),
+            revealed_notes: None,
+            reveal_all_until: None,
         }
     }
 }
 
 impl PasswordManagerApp {
-    pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
-        Self::default()
+    pub fn new(_cc: &eframe::CreationContext<'_>, session: SharedSession) -> Self {
+        let mut app = Self {
+            session,
+            ..Self::default()
+        };
+        if let Some(path) = default_vault_path().filter(|p| p.exists()) {
+            app.new_vault_path = path.display().to_string();
+        }
+        app.kdf_params = detect_kdf_params();
+        app.generator_options = app.settings.generator_options.clone();
+        if !app.settings.onboarding_completed && app.new_vault_path.is_empty() {
+            app.setup_step = Some(SetupStep::Location);
+            if let Some(path) = default_vault_path() {
+                app.new_vault_path = path.display().to_string();
+            }
+        }
+        #[cfg(feature = "global-hotkey")]
+        app.register_lock_hotkey();
+        app
+    }
+
+    fn create_default_vault(&mut self) {
+        let Some(path) = default_vault_path() else {
+            self.error_message = Some("Répertoire de données introuvable".to_string());
+            return;
+        };
+
+        if let Some(parent) = path.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                self.error_message = Some(format!("Erreur lors de la création du dossier: {}", e));
+                return;
+            }
+        }
+
+        self.new_vault_path = path.display().to_string();
+        self.create_vault(false);
     }
 
     fn update_activity(&mut self) {
         self.last_activity = Instant::now();
     }
 
+    /// N'envoie la commande de titre qu'au changement d'état
+    fn update_window_title(&mut self, ctx: &egui::Context) {
+        let dirty = self.dirty_since.is_some();
+        if dirty != self.title_dirty {
+            let title = if dirty { format!("{} *", APP_TITLE) } else { APP_TITLE.to_string() };
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title));
+            self.title_dirty = dirty;
+        }
+    }
+
+    /// Toute entrée réelle (clavier, souris, défilement) repousse le verrouillage auto,
+    /// y compris hors des boutons; une souris immobile au-dessus de la fenêtre ne compte pas
+    fn track_input_activity(&mut self, ctx: &egui::Context) {
+        if ctx.input(|i| !i.events.is_empty() || i.pointer.is_moving()) {
+            self.update_activity();
+        }
+    }
+
+    /// Coffre créé en mémoire seulement, jamais écrit tant qu'il n'est pas « Enregistré sous… »
+    fn is_temporary(&self) -> bool {
+        self.vault.is_some() && self.vault_path.is_none()
+    }
+
     fn check_auto_lock(&mut self) {
-        if self.vault.is_some() && !self.is_locked {
+        // Sans fichier, le déverrouillage serait impossible: un coffre temporaire reste ouvert
+        // 0 = jamais
+        if self.vault.is_some() && !self.is_locked && !self.is_temporary() && self.auto_lock_seconds() > 0 {
             let elapsed = self.last_activity.elapsed();
-            if elapsed > Duration::from_secs(self.auto_lock_seconds) {
+            if elapsed > Duration::from_secs(self.auto_lock_seconds()) {
                 self.lock_vault();
             }
         }
     }
 
+    /// (Ré)enregistre le raccourci des préférences; un échec n'empêche pas l'application de fonctionner
+    #[cfg(feature = "global-hotkey")]
+    fn register_lock_hotkey(&mut self) {
+        // Libère l'ancien raccourci avant d'en demander un nouveau
+        self.lock_hotkey = None;
+        match LockHotkey::register(&self.settings.lock_hotkey) {
+            Ok(hotkey) => self.lock_hotkey = Some(hotkey),
+            Err(e) => self.error_message = Some(e),
+        }
+    }
+
+    /// Retient l'entrée demandée par un lien, si l'utilisateur a activé les liens `mdp://`
+    #[cfg(feature = "deep-link")]
+    pub fn handle_deep_link(&mut self, link: Option<DeepLink>) {
+        if !self.settings.deep_links_enabled {
+            return;
+        }
+        if let Some(DeepLink::Entry(id)) = link {
+            self.pending_deep_link = Some(id);
+        }
+    }
+
+    /// Sélectionne l'entrée du lien une fois le coffre déverrouillé
+    #[cfg(feature = "deep-link")]
+    fn apply_deep_link(&mut self) {
+        if self.screen != Screen::Main || self.vault.is_none() {
+            return;
+        }
+        let Some(id) = self.pending_deep_link.take() else {
+            return;
+        };
+
+        if self.vault.as_ref().and_then(|v| v.get_entry(id)).is_some() {
+            self.selected_entry = Some(id);
+            self.table_view = false;
+        } else {
+            self.warn("Entrée du lien introuvable dans ce coffre".to_string());
+        }
+    }
+
+    #[cfg(feature = "global-hotkey")]
+    fn check_lock_hotkey(&mut self) {
+        let pressed = self.lock_hotkey.as_ref().map_or(false, |h| h.pressed());
+        if pressed && self.screen == Screen::Main && !self.is_locked && !self.is_temporary() {
+            self.lock_vault();
+        }
+    }
+
+    /// Délai de verrouillage du coffre ouvert, réglé dans ses préférences (0 = jamais)
+    fn auto_lock_seconds(&self) -> u64 {
+        self.vault.as_ref().map_or(0, |vault| vault.settings.auto_lock_seconds)
+    }
+
+    /// Avertissement si le coffre peut rester ouvert trop longtemps sans activité
+    fn auto_lock_warning(&self) -> Option<&'static str> {
+        if self.auto_lock_seconds() == 0 {
+            Some("Verrouillage automatique désactivé: le coffre reste ouvert indéfiniment")
+        } else if self.auto_lock_seconds() > AUTO_LOCK_SAFE_MAX_SECS {
+            Some("Délai de verrouillage très long: le coffre peut rester ouvert sans surveillance")
+        } else {
+            None
+        }
+    }
+
+    fn is_revealing_all(&self) -> bool {
+        self.reveal_all_until.is_some()
+    }
+
+    /// Masque tout, y compris les affichages individuels
+    fn hide_all_passwords(&mut self) {
+        self.reveal_all_until = None;
+        self.revealed_password = None;
+    }
+
+    /// Délai avant la prochaine échéance à traiter (verrouillage, effacement du presse-papiers,
+    /// sauvegarde…); `None` si seule une saisie peut changer quelque chose
+    fn next_wakeup(&self) -> Option<Duration> {
+        let mut deadlines: Vec<Instant> = Vec::new();
+        deadlines.extend(self.clipboard.clear_deadline());
+        deadlines.extend(self.reveal_all_until);
+        deadlines.extend(self.last_copied.as_ref().map(|last| last.copied_at + LAST_COPIED_MAX_AGE));
+        if self.vault_path.is_some() {
+            deadlines.extend(self.dirty_since.map(|since| since + Duration::from_secs(AUTOSAVE_DELAY_SECS)));
+        }
+        if self.vault.is_some() && !self.is_locked && !self.is_temporary() && self.auto_lock_seconds() > 0 {
+            // `check_auto_lock` attend que le délai soit strictement dépassé
+            let lock_at = self.last_activity + Duration::from_secs(self.auto_lock_seconds());
+            deadlines.push(lock_at + Duration::from_millis(10));
+        }
+
+        let now = Instant::now();
+        let wakeup = deadlines.into_iter().map(|d| d.saturating_duration_since(now)).min();
+
+        #[cfg(feature = "global-hotkey")]
+        if self.lock_hotkey.is_some() {
+            return Some(wakeup.map_or(HOTKEY_POLL, |w| w.min(HOTKEY_POLL)));
+        }
+        wakeup
+    }
+
+    /// Masquage forcé à l'échéance ou dès que la fenêtre perd le focus
+    fn check_reveal_all(&mut self, ctx: &egui::Context) {
+        let Some(until) = self.reveal_all_until else {
+            return;
+        };
+
+        let focused = ctx.input(|i| i.viewport().focused).unwrap_or(true);
+        if Instant::now() >= until || !focused {
+            self.hide_all_passwords();
+        }
+    }
+
     fn check_clipboard_clear(&mut self) {
-        if let Some(clear_time) = self.clipboard_clear_time {
-            if Instant::now() > clear_time {
-                if let Some(clipboard) = &mut self.clipboard {
-                    let _ = clipboard.set_text("");
+        if self.last_copied.as_ref().map_or(false, |last| last.copied_at.elapsed() >= LAST_COPIED_MAX_AGE) {
+            self.last_copied = None;
+        }
+
+        if self.clipboard.clear_if_due(Instant::now()) == Some(ClearOutcome::Cleared)
+            && self.settings.notify_clipboard_cleared
+        {
+            self.notify("Presse-papiers effacé".to_string());
+        }
+    }
+
+    fn notify(&mut self, message: String) {
+        self.toasts.push(Toast {
+            message,
+            warning: false,
+            created_at: Instant::now(),
+        });
+    }
+
+    /// Comme `notify`, pour ce qui mérite l'attention sans bloquer
+    fn warn(&mut self, message: String) {
+        self.toasts.push(Toast {
+            message,
+            warning: true,
+            created_at: Instant::now(),
+        });
+    }
+
+    fn show_toasts(&mut self, ctx: &egui::Context) {
+        self.toasts.retain(|t| t.created_at.elapsed() < TOAST_DURATION);
+        if self.toasts.is_empty() {
+            return;
+        }
+
+        let mut dismissed = None;
+
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-10.0, -10.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                for (i, toast) in self.toasts.iter().enumerate() {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            if toast.warning {
+                                ui.colored_label(
+                                    egui::Color32::from_rgb(255, 193, 7),
+                                    format!("⚠️ {}", toast.message),
+                                );
+                            } else {
+                                ui.label(format!("✓ {}", toast.message));
+                            }
+                            if ui.small_button("✖").clicked() {
+                                dismissed = Some(i);
+                            }
+                        });
+                    });
                 }
-                self.clipboard_clear_time = None;
-            }
+            });
+
+        if let Some(i) = dismissed {
+            self.toasts.remove(i);
         }
+
+        ctx.request_repaint_after(Duration::from_millis(250));
     }
 
     fn add_audit(&mut self, action: AuditAction) {
         self.audit_log.push(AuditEntry::new(action));
     }
 
-    fn create_vault(&mut self) {
+    fn create_vault(&mut self, overwrite: bool) {
         if self.master_password.len() < 8 {
             self.error_message = Some("Le mot de passe maître doit contenir au moins 8 caractères".to_string());
             return;
@@ -135,16 +800,29 @@ impl PasswordManagerApp {
         }
 
         let path = PathBuf::from(&self.new_vault_path);
+        if let Err(e) = resolve_vault_path(&path) {
+            self.error_message = Some(e.to_string());
+            return;
+        }
+
+        if path.exists() && !overwrite {
+            self.confirm_overwrite = true;
+            return;
+        }
+
         let vault = Vault::new();
 
-        match save_vault(&vault, &path, &self.master_password) {
-            Ok(_) => {
+        match create_vault_file_with_params(&vault, &path, &self.master_password, self.kdf_params.clone()) {
+            Ok(session) => {
+                self.set_session(Some(session));
+                self.master_fingerprint = MasterFingerprint::new(&self.master_password).ok();
                 self.vault = Some(vault);
                 self.vault_path = Some(path);
+                self.vault_slot = VaultSlot::Primary;
                 self.screen = Screen::Main;
                 self.master_password.clear();
                 self.new_vault_path.clear();
-                self.success_message = Some("Coffre créé avec succès!".to_string());
+                self.notify("Coffre créé avec succès!".to_string());
                 self.add_audit(AuditAction::VaultCreated);
                 self.update_search();
             }
@@ -154,6 +832,17 @@ impl PasswordManagerApp {
         }
     }
 
+    fn create_temporary_vault(&mut self) {
+        self.set_session(None);
+        self.vault = Some(Vault::new());
+        self.vault_path = None;
+        self.vault_slot = VaultSlot::Primary;
+        self.screen = Screen::Main;
+        self.master_password.clear();
+        self.notify("Coffre temporaire: rien n'est écrit sur le disque".to_string());
+        self.update_search();
+    }
+
     fn open_vault(&mut self) {
         if self.new_vault_path.is_empty() {
             self.error_message = Some("Veuillez spécifier un chemin de coffre".to_string());
@@ -161,84 +850,431 @@ impl PasswordManagerApp {
         }
 
         let path = PathBuf::from(&self.new_vault_path);
+        self.start_load(path, LoadKind::Open);
+    }
 
-        match load_vault(&path, &self.master_password) {
-            Ok(vault) => {
-                self.vault = Some(vault);
-                self.vault_path = Some(path);
+    /// Lance `load_vault` en arrière-plan pour garder l'interface réactive
+    fn start_load(&mut self, path: PathBuf, kind: LoadKind) {
+        if self.pending_load.is_some() {
+            return;
+        }
+        // Un dossier ou une boucle de liens donnerait sinon une erreur système obscure
+        if let Err(e) = resolve_vault_path(&path) {
+            self.error_message = Some(e.to_string());
+            return;
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let thread_path = path.clone();
+        let mut password = std::mem::take(&mut self.master_password);
+
+        thread::spawn(move || {
+            // Le récepteur peut avoir disparu si la fenêtre a été fermée ou l'ouverture annulée
+            let progress = |stage| {
+                let _ = sender.send(LoadMessage::Progress(stage));
+            };
+            let result = open_vault_file_with_progress(&thread_path, &password, progress)
+                .map(|loaded| (loaded, MasterFingerprint::new(&password).ok()));
+            password.zeroize();
+            let _ = sender.send(LoadMessage::Done(result));
+        });
+
+        self.pending_load = Some(PendingLoad {
+            receiver,
+            path,
+            kind,
+            stage: LoadStage::Reading,
+        });
+    }
+
+    /// Indicateur de chargement; l'annulation abandonne le résultat du fil en cours
+    fn show_load_progress(&mut self, ui: &mut egui::Ui) {
+        let Some(pending) = &self.pending_load else {
+            return;
+        };
+
+        let stage = pending.stage;
+        ui.horizontal(|ui| {
+            ui.spinner();
+            ui.label(stage.label());
+            if ui.button("❌ Annuler").clicked() {
+                self.pending_load = None;
+            }
+        });
+    }
+
+    fn poll_pending_load(&mut self) {
+        let result = loop {
+            let Some(pending) = &mut self.pending_load else {
+                return;
+            };
+
+            match pending.receiver.try_recv() {
+                Ok(LoadMessage::Progress(stage)) => pending.stage = stage,
+                Ok(LoadMessage::Done(result)) => break result,
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {
+                    break Err("Le chargement a été interrompu".into())
+                }
+            }
+        };
+
+        let Some(pending) = self.pending_load.take() else {
+            return;
+        };
+
+        match (pending.kind, result) {
+            (LoadKind::Open, Ok((loaded, fingerprint))) => {
+                self.finish_open(loaded, fingerprint, pending.path);
+            }
+            (_, Err(e)) if e.is_wrong_password() => {
+                self.error_message = Some("Mot de passe incorrect".to_string());
+            }
+            (LoadKind::Open, Err(e)) => {
+                self.error_message = Some(format!("Erreur: {}", e));
+            }
+            (LoadKind::Unlock, Ok((loaded, fingerprint))) => {
+                let restored = self.restore_unsaved(&loaded.session);
+                self.master_fingerprint = fingerprint;
+                self.set_session(Some(loaded.session));
+                self.vault = Some(restored.unwrap_or(loaded.vault));
+                self.vault_slot = loaded.slot;
+                self.is_locked = false;
                 self.screen = Screen::Main;
-                self.master_password.clear();
-                self.new_vault_path.clear();
-                self.success_message = Some("Coffre ouvert avec succès!".to_string());
-                self.add_audit(AuditAction::VaultOpened);
+                let timing = self.record_derive_time(loaded.derive_time);
+                self.notify(format!("Coffre déverrouillé{}", timing));
+                self.update_activity();
                 self.update_search();
+                self.check_master_reuse();
             }
-            Err(e) => {
-                self.error_message = Some(format!("Erreur: {}", e));
+            (LoadKind::Unlock, Err(e)) => {
+                self.error_message = Some(format!("Impossible de déverrouiller: {}", e));
             }
         }
     }
 
+    fn finish_open(&mut self, loaded: LoadedVault, fingerprint: Option<MasterFingerprint>, path: PathBuf) {
+        let restored = self.restore_unsaved(&loaded.session);
+        self.master_fingerprint = fingerprint;
+        self.set_session(Some(loaded.session));
+        self.vault = Some(restored.unwrap_or(loaded.vault));
+        self.vault_slot = loaded.slot;
+        self.vault_path = Some(path);
+        self.screen = Screen::Main;
+        self.new_vault_path.clear();
+        let timing = self.record_derive_time(loaded.derive_time);
+        self.notify(format!("Coffre ouvert avec succès{}", timing));
+        self.add_audit(AuditAction::VaultOpened);
+        if let Some(from) = loaded.migrated_from {
+            self.add_audit(AuditAction::VaultMigrated { from, to: VAULT_FILE_VERSION });
+        }
+        self.update_search();
+        self.check_master_reuse();
+        self.health_banner = self.health_summary();
+    }
+
+    /// Retient la durée de dérivation et la formate pour la notification (« en 620 ms »)
+    fn record_derive_time(&mut self, derive_time: Option<Duration>) -> String {
+        let Some(ms) = derive_time.map(|d| d.as_millis()) else {
+            return String::new();
+        };
+        self.last_derive_ms = Some(ms);
+
+        let hint = if ms < KDF_FAST_MS {
+            " — dérivation rapide: paramètres KDF faibles pour cette machine"
+        } else if ms > KDF_SLOW_MS {
+            " — dérivation lente: paramètres KDF lourds pour cette machine"
+        } else {
+            ""
+        };
+        format!(" en {} ms{}", ms, hint)
+    }
+
+    /// Problèmes notables du coffre, ou `None` s'il n'y a rien à signaler
+    fn health_summary(&self) -> Option<String> {
+        let vault = self.vault.as_ref()?;
+        let logins = vault.entries.iter().filter(|e| !e.is_secure_note()).count();
+        if logins == 0 {
+            return None;
+        }
+
+        let weak = vault.weak_entries().len();
+        let reused = vault.find_duplicate_passwords().len();
+
+        let mut issues = Vec::new();
+        if weak as f32 / logins as f32 >= WEAK_FRACTION_WARNING {
+            issues.push(format!("{} mot(s) de passe faible(s) ou moyen(s) sur {}", weak, logins));
+        }
+        if reused > 0 {
+            issues.push(format!("{} mot(s) de passe partagé(s) entre plusieurs entrées", reused));
+        }
+
+        if issues.is_empty() {
+            None
+        } else {
+            Some(issues.join(", "))
+        }
+    }
+
     fn lock_vault(&mut self) {
+        if self.is_temporary() {
+            self.error_message = Some("Enregistrez le coffre temporaire avant de le verrouiller".to_string());
+            return;
+        }
+
+        // Un échec d'écriture n'empêche jamais le verrouillage: le déverrouillage relisant le
+        // fichier, les modifications sont gardées chiffrées avec la clé de session
+        if !self.save_now() {
+            let session = self.session.lock().unwrap_or_else(|e| e.into_inner());
+            self.unsaved_snapshot = match (&self.vault, session.as_ref()) {
+                (Some(vault), Some(session)) => seal_snapshot(vault, session).ok(),
+                _ => None,
+            };
+            drop(session);
+            if let Some(message) = self.error_message.take() {
+                self.error_message = Some(format!(
+                    "{}. Coffre verrouillé quand même: les modifications seront restaurées au déverrouillage.",
+                    message
+                ));
+            }
+        }
+        self.set_session(None);
+        self.unlock_preview = self.vault_path.as_deref().and_then(|p| load_vault_metadata(p).ok());
         self.is_locked = true;
         self.screen = Screen::Unlock;
         self.master_password.clear();
+        self.backup_password.zeroize();
+        // SecureKey efface l'empreinte à la destruction
+        self.master_fingerprint = None;
         self.selected_entry = None;
+        self.last_copied = None;
+        self.hide_sealed_notes();
+        self.editor_show_password = false;
+        self.hide_all_passwords();
         self.add_audit(AuditAction::VaultLocked);
     }
 
-    fn unlock_vault(&mut self) {
-        if let Some(path) = &self.vault_path.clone() {
-            match load_vault(path, &self.master_password) {
-                Ok(vault) => {
-                    self.vault = Some(vault);
-                    self.is_locked = false;
-                    self.screen = Screen::Main;
-                    self.master_password.clear();
-                    self.success_message = Some("Coffre déverrouillé".to_string());
-                    self.update_activity();
-                    self.update_search();
-                }
-                Err(e) => {
-                    self.error_message = Some(format!("Mot de passe incorrect: {}", e));
-                }
+    /// Modifications gardées au verrouillage, si c'est bien le même coffre qui vient d'être rouvert
+    fn restore_unsaved(&mut self, session: &VaultSession) -> Option<Vault> {
+        let snapshot = self.unsaved_snapshot.as_ref()?;
+        match open_snapshot(snapshot, session) {
+            Ok(vault) => {
+                self.unsaved_snapshot = None;
+                // `dirty_since` et `save_failed` sont restés levés: l'enregistrement sera retenté
+                self.notify("Modifications non enregistrées restaurées".to_string());
+                Some(vault)
+            }
+            Err(_) => {
+                // Autre emplacement: l'instantané attend le déverrouillage du coffre concerné
+                self.dirty_since = None;
+                self.save_failed = false;
+                None
             }
         }
     }
 
+    /// Repère les entrées dont le mot de passe est identique au mot de passe maître
+    fn check_master_reuse(&mut self) {
+        self.master_reused_by = match (&self.vault, &self.master_fingerprint) {
+            (Some(vault), Some(fingerprint)) => vault
+                .entries
+                .iter()
+                .filter(|e| !e.is_secure_note() && fingerprint.matches(&e.password))
+                .map(|e| e.id)
+                .collect(),
+            _ => Vec::new(),
+        };
+    }
+
+    fn unlock_vault(&mut self) {
+        if let Some(path) = self.vault_path.clone() {
+            self.start_load(path, LoadKind::Unlock);
+        }
+    }
+
     fn update_search(&mut self) {
         if let Some(vault) = &self.vault {
-            self.filtered_entries = vault
+            let mut entries: Vec<(&Entry, i32)> = vault
                 .entries
                 .iter()
-                .filter(|e| {
+                .filter_map(|e| {
                     if self.search_query.is_empty() {
-                        true
+                        Some((e, 0))
                     } else {
-                        e.matches_search(&self.search_query)
+                        e.search_score(&self.search_query).map(|score| (e, score))
                     }
                 })
-                .map(|e| e.id)
                 .collect();
+
+            match self.sort_order {
+                SortOrder::Name => {
+                    entries.sort_by_key(|(e, _)| e.name.to_lowercase());
+                    // Le groupe "#" passe après les lettres
+                    if self.show_sections {
+                        entries.sort_by_key(|(e, _)| e.section_letter() == "#");
+                    }
+                }
+                SortOrder::RecentlyModified => {
+                    entries.sort_by(|(a, _), (b, _)| b.modified_at.cmp(&a.modified_at));
+                }
+                SortOrder::MostUsed => {
+                    entries.sort_by(|(a, _), (b, _)| b.use_count.cmp(&a.use_count));
+                }
+            }
+
+            // Tri stable: à pertinence égale, l'ordre choisi est conservé
+            entries.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+
+            self.filtered_entries = entries.iter().map(|(e, _)| e.id).collect();
         }
     }
 
     fn copy_to_clipboard(&mut self, text: &str) {
-        if let Some(clipboard) = &mut self.clipboard {
-            if clipboard.set_text(text).is_ok() {
-                self.success_message = Some(format!(
-                    "Copié! Sera effacé dans {} secondes",
-                    self.clipboard_clear_delay
-                ));
-                self.clipboard_clear_time = Some(Instant::now() + Duration::from_secs(self.clipboard_clear_delay));
-            }
+        self.copy_to_clipboard_with_delay(text, None);
+    }
+
+    /// Copie avec un délai d'effacement propre à l'entrée, sinon le délai global
+    fn copy_to_clipboard_with_delay(&mut self, text: &str, delay_override: Option<u64>) {
+        let delay = delay_override.unwrap_or(self.clipboard_clear_delay);
+        if !self.clipboard.is_available() {
+            return;
+        }
+        match self.clipboard.copy(text, Duration::from_secs(delay), Instant::now()) {
+            Ok(()) => self.notify(format!("Copié! Sera effacé dans {} secondes", delay)),
+            Err(_) => self.error_message = Some("Impossible d'écrire dans le presse-papiers".to_string()),
         }
     }
 
-    fn show_welcome(&mut self, ui: &mut egui::Ui) {
-        ui.vertical_centered(|ui| {
-            ui.add_space(50.0);
-            ui.heading("🔐 Gestionnaire de Mots de Passe");
+    /// Nouvelle tentative d'initialisation, par exemple après le démarrage d'un portail Wayland
+    fn retry_clipboard(&mut self) {
+        self.clipboard = ClipboardManager::system();
+        if self.clipboard.is_available() {
+            self.notify("Presse-papiers disponible".to_string());
+        } else {
+            self.error_message = Some(CLIPBOARD_UNAVAILABLE.to_string());
+        }
+    }
+
+    fn set_session(&mut self, session: Option<VaultSession>) {
+        *self.session.lock().unwrap_or_else(|e| e.into_inner()) = session;
+    }
+
+    /// Note une modification; l'écriture sur disque est regroupée par `autosave_if_due`
+    fn mark_dirty(&mut self) {
+        if self.dirty_since.is_none() {
+            self.dirty_since = Some(Instant::now());
+        }
+    }
+
+    fn autosave_if_due(&mut self) {
+        if self.vault_path.is_none() {
+            return;
+        }
+
+        if let Some(since) = self.dirty_since {
+            if since.elapsed() >= Duration::from_secs(AUTOSAVE_DELAY_SECS) {
+                self.save_now();
+            }
+        }
+    }
+
+    /// Écrit les modifications en attente; `false` si elles n'existent encore qu'en mémoire
+    fn save_now(&mut self) -> bool {
+        if self.dirty_since.is_none() {
+            return true;
+        }
+
+        let session = self.session.lock().unwrap_or_else(|e| e.into_inner());
+        if let (Some(vault), Some(path), Some(session)) = (&self.vault, &self.vault_path, session.as_ref()) {
+            match save_vault_session(vault, path, session) {
+                Ok(_) => {
+                    self.dirty_since = None;
+                    self.save_failed = false;
+                }
+                Err(e) => {
+                    self.error_message = Some(match e {
+                        StorageError::Io(_) => format!("Échec de l'enregistrement — espace disque ? ({})", e),
+                        _ => format!("Échec de l'enregistrement: {}", e),
+                    });
+                    self.save_failed = true;
+                    // Toujours en attente: réessayer au prochain délai plutôt qu'à chaque image
+                    self.dirty_since = Some(Instant::now());
+                }
+            }
+        }
+        self.dirty_since.is_none()
+    }
+
+    /// Compte les copies du mot de passe d'une entrée
+    fn record_use(&mut self, entry_id: Uuid) {
+        if let Some(vault) = &mut self.vault {
+            if let Some(entry) = vault.entries.iter_mut().find(|e| e.id == entry_id) {
+                entry.use_count += 1;
+                self.mark_dirty();
+                if self.sort_order == SortOrder::MostUsed {
+                    self.update_search();
+                }
+            }
+        }
+    }
+
+    /// Copie un mot de passe et prévient une fois des gestionnaires d'historique
+    fn copy_password_to_clipboard(&mut self, text: &str, delay_override: Option<u64>) {
+        self.copy_to_clipboard_with_delay(text, delay_override);
+        self.last_copied = Some(LastCopied {
+            text: text.to_string(),
+            delay_override,
+            copied_at: Instant::now(),
+        });
+        if !self.settings.clipboard_warning_dismissed {
+            self.show_clipboard_warning = true;
+        }
+    }
+
+    /// Recopie le dernier secret et relance le délai d'effacement
+    fn recopy_last(&mut self) {
+        let Some((mut text, delay_override)) = self
+            .last_copied
+            .as_ref()
+            .filter(|last| last.copied_at.elapsed() < LAST_COPIED_MAX_AGE)
+            .map(|last| (last.text.clone(), last.delay_override))
+        else {
+            self.notify("Rien à recopier".to_string());
+            return;
+        };
+
+        self.copy_to_clipboard_with_delay(&text, delay_override);
+        text.zeroize();
+    }
+
+    fn show_clipboard_warning_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new("⚠️ Historique du presse-papiers")
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label("Le presse-papiers est vidé automatiquement après le délai configuré.");
+                ui.label("Les gestionnaires d'historique du presse-papiers (Windows+V, KDE Klipper,");
+                ui.label("GPaste, Maccy…) peuvent toutefois conserver une copie du mot de passe.");
+                ui.label("Pensez à les désactiver ou à exclure cette application.");
+
+                ui.add_space(10.0);
+
+                if ui.button("Compris, ne plus afficher").clicked() {
+                    self.show_clipboard_warning = false;
+                    self.settings.clipboard_warning_dismissed = true;
+                    self.save_settings();
+                }
+            });
+    }
+
+    fn show_welcome(&mut self, ui: &mut egui::Ui) {
+        if self.setup_step.is_some() {
+            self.show_setup(ui);
+            return;
+        }
+
+        ui.vertical_centered(|ui| {
+            ui.add_space(50.0);
+            ui.heading("🔐 Gestionnaire de Mots de Passe");
             ui.add_space(30.0);
 
             ui.group(|ui| {
@@ -248,38 +1284,298 @@ impl PasswordManagerApp {
 
                 ui.add_space(10.0);
                 ui.label("Mot de passe maître:");
-                ui.add(egui::TextEdit::singleline(&mut self.master_password).password(true));
+                let response = ui.add(egui::TextEdit::singleline(&mut self.master_password).password(true));
+                self.show_caps_lock_hint(ui, &response);
 
                 ui.add_space(20.0);
 
-                ui.horizontal(|ui| {
-                    if ui.button("📁 Ouvrir un coffre").clicked() {
-                        self.open_vault();
-                        self.update_activity();
+                if self.pending_load.is_some() {
+                    self.show_load_progress(ui);
+                } else {
+                    ui.horizontal(|ui| {
+                        if ui.button("📁 Ouvrir un coffre").clicked() {
+                            self.open_vault();
+                        }
+
+                        if ui.button("➕ Créer un coffre").clicked() {
+                            self.create_vault(false);
+                        }
+                    });
+
+                    if ui.button("🧩 Ouvrir avec des parts de clé").clicked() {
+                        self.show_share_unlock = true;
                     }
 
-                    if ui.button("➕ Créer un coffre").clicked() {
-                        self.create_vault();
-                        self.update_activity();
+                    if ui
+                        .button("🧪 Coffre temporaire")
+                        .on_hover_text("Coffre en mémoire uniquement, enregistrable plus tard")
+                        .clicked()
+                    {
+                        self.create_temporary_vault();
                     }
-                });
+
+                    if let Some(default_path) = default_vault_path().filter(|p| !p.exists()) {
+                        ui.add_space(10.0);
+                        if ui
+                            .button("✨ Créer à l'emplacement par défaut")
+                            .on_hover_text(default_path.display().to_string())
+                            .clicked()
+                        {
+                            self.create_default_vault();
+                        }
+                    }
+                }
             });
 
+            if self.confirm_overwrite {
+                self.show_overwrite_confirmation(ui.ctx());
+            }
+
+            if self.show_share_unlock {
+                self.show_share_unlock_window(ui.ctx());
+            }
+
+            if self.kdf_params.is_reduced() {
+                ui.add_space(10.0);
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 193, 7),
+                    format!(
+                        "⚠️ Mémoire faible: Argon2 limité à {} MiB pour les nouveaux coffres (protection réduite)",
+                        self.kdf_params.memory_cost / 1024
+                    ),
+                );
+            }
+
             ui.add_space(20.0);
             ui.label("⚠️ Application locale - Aucune donnée n'est envoyée sur internet");
         });
     }
 
+    fn show_setup(&mut self, ui: &mut egui::Ui) {
+        let Some(step) = self.setup_step else {
+            return;
+        };
+        let mut next = None;
+
+        ui.vertical_centered(|ui| {
+            ui.add_space(50.0);
+            ui.heading("👋 Bienvenue");
+            ui.label(format!("Étape {}/{}: {}", step.index() + 1, SetupStep::ALL.len(), step.label()));
+            ui.add_space(20.0);
+
+            ui.group(|ui| {
+                ui.set_width(420.0);
+                match step {
+                    SetupStep::Location => {
+                        ui.label("Où enregistrer le coffre?");
+                        ui.text_edit_singleline(&mut self.new_vault_path);
+                        ui.weak("Le fichier est chiffré: il peut être placé dans un dossier synchronisé.");
+                    }
+                    SetupStep::Kdf => {
+                        ui.label(format!(
+                            "Argon2id: {} MiB de mémoire, {} passe(s)",
+                            self.kdf_params.memory_cost / 1024,
+                            self.kdf_params.time_cost
+                        ));
+                        if self.kdf_params.is_reduced() {
+                            ui.colored_label(
+                                egui::Color32::from_rgb(255, 193, 7),
+                                "⚠️ Mémoire faible: protection réduite sur cette machine",
+                            );
+                        }
+                        if ui
+                            .add(egui::Slider::new(&mut self.kdf_params.time_cost, 1..=10).text("Passes"))
+                            .changed()
+                        {
+                            self.setup_kdf_ms = None;
+                        }
+                        if ui.button("⏱ Mesurer").clicked() {
+                            self.measure_kdf();
+                        }
+                        if let Some(ms) = self.setup_kdf_ms {
+                            ui.label(format!("Déverrouillage: environ {} ms", ms));
+                            if ms < KDF_FAST_MS {
+                                ui.weak("Rapide: vous pouvez augmenter les passes.");
+                            } else if ms > KDF_SLOW_MS {
+                                ui.weak("Lent: chaque déverrouillage prendra plusieurs secondes.");
+                            }
+                        }
+                    }
+                    SetupStep::Password => {
+                        ui.label("Mot de passe maître:");
+                        ui.add(egui::TextEdit::singleline(&mut self.master_password).password(true));
+                        ui.label("Confirmation:");
+                        ui.add(egui::TextEdit::singleline(&mut self.setup_confirm_password).password(true));
+
+                        let strength = estimate_strength(&self.master_password);
+                        ui.horizontal(|ui| {
+                            ui.label("Force:");
+                            ui.colored_label(strength.color(), strength.label());
+                        });
+                        ui.weak("Il ne peut pas être récupéré en cas d'oubli.");
+                    }
+                    SetupStep::Import => {
+                        ui.label("Fichier CSV à importer (laisser vide pour commencer sans):");
+                        ui.text_edit_singleline(&mut self.setup_import_path);
+                        ui.weak("Colonnes attendues: name, login, password, url, notes, tags");
+                    }
+                }
+
+                ui.add_space(20.0);
+                ui.horizontal(|ui| {
+                    if step.index() > 0 && ui.button("◀ Retour").clicked() {
+                        next = Some(SetupStep::ALL[step.index() - 1]);
+                    }
+                    if step == SetupStep::Import {
+                        if ui.button("✅ Créer le coffre").clicked() {
+                            self.finish_setup();
+                        }
+                    } else if ui.button("Suivant ▶").clicked() {
+                        if self.validate_setup_step(step) {
+                            next = Some(SetupStep::ALL[step.index() + 1]);
+                        }
+                    }
+                    if ui.button("Passer l'assistant").clicked() {
+                        self.complete_setup();
+                    }
+                });
+            });
+        });
+
+        if let Some(next) = next {
+            self.setup_step = Some(next);
+        }
+    }
+
+    fn validate_setup_step(&mut self, step: SetupStep) -> bool {
+        let error = match step {
+            SetupStep::Location if self.new_vault_path.trim().is_empty() => {
+                Some("Veuillez spécifier un chemin pour le coffre")
+            }
+            SetupStep::Location if PathBuf::from(&self.new_vault_path).exists() => {
+                Some("Un fichier existe déjà à cet emplacement: passez l'assistant pour l'ouvrir")
+            }
+            SetupStep::Password if self.master_password.len() < 8 => {
+                Some("Le mot de passe maître doit contenir au moins 8 caractères")
+            }
+            SetupStep::Password if self.master_password != self.setup_confirm_password => {
+                Some("Les mots de passe ne correspondent pas")
+            }
+            _ => None,
+        };
+
+        match error {
+            Some(message) => {
+                self.error_message = Some(message.to_string());
+                false
+            }
+            None => true,
+        }
+    }
+
+    /// Durée d'une dérivation avec les paramètres choisis, soit le coût d'un déverrouillage
+    fn measure_kdf(&mut self) {
+        let start = Instant::now();
+        match derive_key("calibration", &generate_salt(), &self.kdf_params) {
+            Ok(mut key) => {
+                key.zeroize();
+                self.setup_kdf_ms = Some(start.elapsed().as_millis());
+            }
+            Err(e) => self.error_message = Some(format!("Erreur lors de la mesure: {}", e)),
+        }
+    }
+
+    fn finish_setup(&mut self) {
+        let path = PathBuf::from(&self.new_vault_path);
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                self.error_message = Some(format!("Erreur lors de la création du dossier: {}", e));
+                return;
+            }
+        }
+
+        self.create_vault(false);
+        if self.vault.is_none() {
+            return;
+        }
+
+        if !self.setup_import_path.trim().is_empty() {
+            match import_csv(&PathBuf::from(self.setup_import_path.trim())) {
+                Ok(entries) => {
+                    let count = entries.len();
+                    if let Some(vault) = &mut self.vault {
+                        for entry in entries {
+                            vault.add_entry(entry);
+                        }
+                    }
+                    self.add_audit(AuditAction::ImportCsv);
+                    self.update_search();
+                    self.mark_dirty();
+                    self.notify(format!("{} entrées importées", count));
+                }
+                Err(e) => self.error_message = Some(format!("CSV invalide: {}", e)),
+            }
+        }
+
+        self.complete_setup();
+    }
+
+    fn complete_setup(&mut self) {
+        self.setup_step = None;
+        self.setup_confirm_password.zeroize();
+        self.setup_import_path.clear();
+        self.settings.onboarding_completed = true;
+        self.save_settings();
+    }
+
+    /// egui n'expose pas l'état de Verr. Maj: on le déduit des lettres tapées
+    /// (majuscule sans Maj ou minuscule avec Maj) tant que le champ a le focus.
+    fn show_caps_lock_hint(&mut self, ui: &mut egui::Ui, response: &egui::Response) {
+        if !response.has_focus() {
+            return;
+        }
+
+        ui.input(|i| {
+            for event in &i.events {
+                if let egui::Event::Text(text) = event {
+                    if let Some(c) = text.chars().rev().find(|c| c.is_alphabetic()) {
+                        if c.is_uppercase() != c.is_lowercase() {
+                            self.caps_lock_on = c.is_uppercase() != i.modifiers.shift;
+                        }
+                    }
+                }
+            }
+        });
+
+        if self.caps_lock_on {
+            ui.colored_label(egui::Color32::from_rgb(255, 193, 7), "⚠️ Verr. Maj activé");
+        }
+    }
+
     fn show_unlock(&mut self, ui: &mut egui::Ui) {
         ui.vertical_centered(|ui| {
             ui.add_space(100.0);
             ui.heading("🔒 Coffre verrouillé");
+            if let Some(meta) = &self.unlock_preview {
+                ui.weak(format!(
+                    "{} entrée(s), modifié le {}",
+                    meta.entry_count,
+                    self.settings.format_timestamp(&meta.modified_at)
+                ))
+                .on_hover_text("Lu en clair dans le fichier, sans vérification: indicatif");
+            }
             ui.add_space(30.0);
 
             ui.group(|ui| {
                 ui.set_width(300.0);
+                if self.pending_load.is_some() {
+                    self.show_load_progress(ui);
+                    return;
+                }
+
                 ui.label("Mot de passe maître:");
                 let response = ui.add(egui::TextEdit::singleline(&mut self.master_password).password(true));
+                self.show_caps_lock_hint(ui, &response);
 
                 if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
                     self.unlock_vault();
@@ -295,69 +1591,334 @@ impl PasswordManagerApp {
     }
 
     fn show_main(&mut self, _ui: &mut egui::Ui, ctx: &egui::Context) {
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::P)) {
+            self.show_palette = !self.show_palette;
+            self.palette_query.clear();
+            self.palette_selected = 0;
+        }
+
+        // Pas Ctrl+Maj+C: egui-winit le transforme en `Event::Copy`, jamais vu comme une touche
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::R)) {
+            self.recopy_last();
+        }
+
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             ui.horizontal(|ui| {
-                ui.heading("🔐 Gestionnaire de Mots de Passe");
+                if let Some(vault) = &self.vault {
+                    let [r, g, b] = vault.settings.color;
+                    ui.heading(
+                        egui::RichText::new(format!(
+                            "🔐 {}",
+                            vault.settings.display_name(self.vault_path.as_deref())
+                        ))
+                        .color(egui::Color32::from_rgb(r, g, b)),
+                    );
+                }
                 ui.separator();
 
-                if ui.button("🔒 Verrouiller").clicked() {
+                if self.is_temporary() {
+                    if ui.button("💾 Enregistrer sous…").clicked() {
+                        self.show_save_as = true;
+                    }
+                } else if ui.button("🔒 Verrouiller").clicked() {
                     self.lock_vault();
                 }
 
+                if let Some(until) = self.reveal_all_until {
+                    let remaining = until.saturating_duration_since(Instant::now()).as_secs();
+                    ui.ctx().request_repaint_after(COUNTDOWN_REPAINT);
+                    if ui.button(format!("🙈 Tout masquer ({}s)", remaining)).clicked() {
+                        self.hide_all_passwords();
+                    }
+                } else if ui
+                    .button("👁 Tout afficher")
+                    .on_hover_text(format!(
+                        "Affiche tous les mots de passe pendant {}s au plus; masqués dès que la fenêtre perd le focus",
+                        self.settings.reveal_all_seconds
+                    ))
+                    .clicked()
+                {
+                    self.revealed_password = None;
+                    self.reveal_all_until =
+                        Some(Instant::now() + Duration::from_secs(self.settings.reveal_all_seconds));
+                }
+
+                ui.toggle_value(&mut self.table_view, "☰ Tableau")
+                    .on_hover_text("Toutes les entrées filtrées en lignes, triables par colonne");
+
                 if ui.button("📊 Audit").clicked() {
                     self.show_audit = !self.show_audit;
-                    self.update_activity();
+                }
+
+                if ui.button("🩺 Santé").clicked() {
+                    self.show_health = !self.show_health;
+                }
+
+                if ui.button("💾 Sauvegarde").clicked() {
+                    self.show_backup = !self.show_backup;
+                }
+
+                if ui.button("📄 Export CSV").clicked() {
+                    self.show_csv_export = !self.show_csv_export;
+                }
+
+                if ui.button("📁 Dossiers").clicked() {
+                    self.show_folders = !self.show_folders;
+                }
+
+                if ui.button("🌐 Ouvrir des sites").clicked() {
+                    self.show_batch_open = !self.show_batch_open;
+                }
+
+                if ui.button("⚙️ Préférences").clicked() {
+                    self.show_settings = !self.show_settings;
+                }
+
+                // Affiché dans les deux emplacements: son absence trahirait une session leurre
+                if !self.is_temporary() && ui.button("🎭 Coffre leurre").clicked()
+                {
+                    self.show_decoy_setup = true;
                 }
 
                 ui.separator();
-                ui.label(format!("⏱️ Verrouillage auto: {}s", self.auto_lock_seconds));
+                let auto_lock = if self.auto_lock_seconds() == 0 {
+                    "⏱️ Verrouillage auto: jamais".to_string()
+                } else {
+                    format!("⏱️ Verrouillage auto: {}s", self.auto_lock_seconds())
+                };
+                match self.auto_lock_warning() {
+                    Some(warning) => {
+                        ui.colored_label(egui::Color32::from_rgb(255, 193, 7), format!("⚠️ {}", auto_lock))
+                            .on_hover_text(warning);
+                    }
+                    None => {
+                        ui.label(auto_lock);
+                    }
+                }
+
+                if self.is_temporary() {
+                    ui.separator();
+                    ui.colored_label(egui::Color32::from_rgb(255, 193, 7), "🧪 Coffre temporaire")
+                        .on_hover_text("Perdu à la fermeture s'il n'est pas enregistré");
+                } else if self.save_failed {
+                    ui.separator();
+                    ui.colored_label(egui::Color32::from_rgb(220, 53, 69), "⚠️ Non enregistré")
+                        .on_hover_text("La dernière sauvegarde a échoué; nouvel essai automatique");
+                } else if self.dirty_since.is_some() {
+                    ui.separator();
+                    ui.weak("● Modifié").on_hover_text("Enregistrement automatique dans quelques secondes");
+                }
+
+                if !self.master_reused_by.is_empty() {
+                    ui.separator();
+                    ui.colored_label(
+                        egui::Color32::from_rgb(220, 53, 69),
+                        format!(
+                            "⚠️ {} entrée(s) réutilisent le mot de passe maître",
+                            self.master_reused_by.len()
+                        ),
+                    );
+                }
             });
         });
 
+        if let Some(summary) = self.health_banner.clone() {
+            egui::TopBottomPanel::top("health_banner").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.colored_label(egui::Color32::from_rgb(255, 193, 7), format!("⚠️ {}", summary));
+                    if ui.button("🩺 Voir le rapport").clicked() {
+                        self.show_health = true;
+                        self.health_banner = None;
+                    }
+                    if ui.small_button("✕").on_hover_text("Masquer jusqu'à la prochaine ouverture").clicked() {
+                        self.health_banner = None;
+                    }
+                });
+            });
+        }
+
+        self.handle_list_keys(ctx);
+
         egui::SidePanel::left("entries_panel").min_width(300.0).show(ctx, |ui| {
             ui.horizontal(|ui| {
                 ui.label("🔍");
                 let response = ui.add(egui::TextEdit::singleline(&mut self.search_query).hint_text("Rechercher..."));
                 if response.changed() {
                     self.update_search();
-                    self.update_activity();
+                }
+                // ↓ depuis la recherche passe la main à la liste
+                if response.has_focus() && ui.input(|i| i.key_pressed(egui::Key::ArrowDown)) {
+                    response.surrender_focus();
+                    if self.selected_entry.map_or(true, |id| !self.filtered_entries.contains(&id)) {
+                        self.selected_entry = self.filtered_entries.first().copied();
+                        self.scroll_to_selected = true;
+                    }
+                }
+            });
+
+            ui.horizontal(|ui| {
+                let previous = (self.sort_order, self.show_sections);
+                egui::ComboBox::from_label("Trier")
+                    .selected_text(self.sort_order.label())
+                    .show_ui(ui, |ui| {
+                        for order in [SortOrder::Name, SortOrder::RecentlyModified, SortOrder::MostUsed] {
+                            ui.selectable_value(&mut self.sort_order, order, order.label());
+                        }
+                        if self.sort_order == SortOrder::Name {
+                            ui.separator();
+                            ui.checkbox(&mut self.show_sections, "Sections A–Z");
+                        }
+                    });
+                if previous != (self.sort_order, self.show_sections) {
+                    self.update_search();
                 }
             });
 
             ui.separator();
 
             if ui.button("➕ Nouvelle entrée").clicked() {
-                self.editing_entry = Some(Entry::new(String::new(), String::new(), String::new()));
-                self.show_entry_modal = true;
-                self.update_activity();
+                self.open_entry_editor(Entry::new(String::new(), String::new(), String::new()));
+            }
+
+            if ui.button("📥 Importer depuis texte").clicked() {
+                self.show_csv_paste = true;
+            }
+
+            if ui.button("➕➕ Créer plusieurs entrées").clicked() {
+                self.batch_drafts.clear();
+                self.show_batch_create = true;
+            }
+
+            if ui.button("🧹 Fusionner les doublons").clicked() {
+                self.open_duplicates_tool();
             }
 
             ui.separator();
 
+            if self.search_query.is_empty() {
+                let quick: Vec<(Uuid, String)> = self
+                    .vault
+                    .as_ref()
+                    .map(|v| {
+                        v.most_used(5)
+                            .into_iter()
+                            .map(|e| (e.id, format!("{} {}", e.display_icon(), e.name)))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                if !quick.is_empty() {
+                    ui.label("⭐ Accès rapide");
+                    for (entry_id, label) in quick {
+                        if ui.selectable_label(self.selected_entry == Some(entry_id), label).clicked() {
+                            self.selected_entry = Some(entry_id);
+                        }
+                    }
+                    ui.separator();
+                }
+            }
+
             egui::ScrollArea::vertical().show(ui, |ui| {
                 let filtered_ids = self.filtered_entries.clone();
+                // Le classement par pertinence d'une recherche mélange les sections
+                let sectioned =
+                    self.show_sections && self.sort_order == SortOrder::Name && self.search_query.is_empty();
+                let mut current_section: Option<String> = None;
+                let mut menu_action: Option<(EntryAction, Uuid)> = None;
                 for entry_id in filtered_ids {
                     if let Some(vault) = &self.vault {
                         if let Some(entry) = vault.get_entry(entry_id) {
+                            if sectioned {
+                                let letter = entry.section_letter();
+                                if current_section.as_ref() != Some(&letter) {
+                                    ui.add_space(4.0);
+                                    ui.strong(&letter);
+                                    ui.separator();
+                                    current_section = Some(letter);
+                                }
+                            }
+
                             let is_selected = self.selected_entry == Some(entry_id);
-                            let response = ui.selectable_label(is_selected, &entry.name);
+                            let mut suffix = String::new();
+                            if entry.is_secure_note() {
+                                suffix.push_str(" 📝");
+                            }
+                            if self.master_reused_by.contains(&entry_id) {
+                                suffix.push_str(" ⚠️");
+                            }
+                            if entry.breach_status.as_ref().map_or(false, |b| b.is_breached()) {
+                                suffix.push_str(" 🚨");
+                            }
+                            if self.is_revealing_all() && !entry.is_secure_note() {
+                                suffix.push_str(&format!(" · {}", entry.password));
+                            }
+                            let color = if entry.is_secure_note() {
+                                ui.visuals().text_color()
+                            } else {
+                                self.password_age(entry).color()
+                            };
+                            let matched = if self.search_query.is_empty() {
+                                None
+                            } else {
+                                entry.match_detail(&self.search_query)
+                            };
+                            let label = entry_list_label(ui, entry, color, matched.as_ref(), &suffix);
+                            let response = ui.selectable_label(is_selected, label);
 
+                            response.context_menu(|ui| {
+                                let mut actions = Vec::new();
+                                if !entry.is_secure_note() && self.clipboard.is_available() {
+                                    actions.push((EntryAction::CopyPassword, "📋 Copier le mot de passe"));
+                                    actions.push((EntryAction::CopyLogin, "📋 Copier l'identifiant"));
+                                }
+                                if !entry.is_secure_note() {
+                                    if entry.link_url().is_some() {
+                                        actions.push((EntryAction::OpenUrl, "🌐 Ouvrir l'URL"));
+                                    }
+                                    actions.push((EntryAction::RotatePassword, "🔄 Renouveler le mot de passe"));
+                                }
+                                actions.push((EntryAction::Edit, "✏️ Modifier"));
+                                actions.push((EntryAction::Duplicate, "📄 Dupliquer"));
+                                actions.push((EntryAction::Delete, "🗑️ Supprimer"));
+
+                                for (action, label) in actions {
+                                    if ui.button(label).clicked() {
+                                        menu_action = Some((action, entry_id));
+                                        ui.close_menu();
+                                    }
+                                }
+                            });
+
+                            if is_selected && self.scroll_to_selected {
+                                response.scroll_to_me(None);
+                            }
                             if response.clicked() {
                                 self.selected_entry = Some(entry_id);
-                                self.update_activity();
                             }
                         }
                     }
                 }
+                // La vue tableau défile aussi jusqu'à la sélection; elle remet l'indicateur à zéro
+                if !self.table_view {
+                    self.scroll_to_selected = false;
+                }
+
+                if let Some((action, entry_id)) = menu_action {
+                    self.apply_entry_action(ui.ctx(), action, entry_id);
+                }
             });
         });
 
         egui::CentralPanel::default().show(ctx, |ui| {
-            if let Some(selected_id) = self.selected_entry {
+            if self.table_view {
+                self.show_entry_table(ui);
+            } else if let Some(selected_id) = self.selected_entry {
                 if let Some(vault) = &self.vault {
                     if let Some(entry) = vault.get_entry(selected_id) {
                         let entry_clone = entry.clone();
-                        self.show_entry_details(ui, &entry_clone);
+                        egui::ScrollArea::vertical().show(ui, |ui| {
+                            self.show_entry_details(ui, &entry_clone);
+                        });
                     }
                 }
             } else {
@@ -376,6 +1937,14 @@ impl PasswordManagerApp {
             self.show_password_generator(ctx);
         }
 
+        if self.show_batch_create {
+            self.show_batch_create_window(ctx);
+        }
+
+        if self.show_csv_paste {
+            self.show_csv_paste_window(ctx);
+        }
+
         if self.confirm_delete.is_some() {
             self.show_delete_confirmation(ctx);
         }
@@ -383,35 +1952,473 @@ impl PasswordManagerApp {
         if self.show_audit {
             self.show_audit_window(ctx);
         }
-    }
 
-    fn show_entry_details(&mut self, ui: &mut egui::Ui, entry: &Entry) {
-        ui.heading(&entry.name);
-        ui.separator();
+        if self.show_decoy_setup {
+            self.show_decoy_setup_window(ctx);
+        }
 
-        ui.horizontal(|ui| {
-            if ui.button("✏️ Modifier").clicked() {
-                self.editing_entry = Some(entry.clone());
-                self.show_entry_modal = true;
-                self.update_activity();
-            }
+        if self.show_settings {
+            self.show_settings_window(ctx);
+        }
 
-            if ui.button("🗑️ Supprimer").clicked() {
-                self.confirm_delete = Some(entry.id);
-                self.update_activity();
-            }
-        });
+        if self.show_backup {
+            self.show_backup_window(ctx);
+        }
 
-        ui.add_space(20.0);
+        if self.show_csv_export {
+            self.show_csv_export_window(ctx);
+        }
 
-        ui.group(|ui| {
-            ui.label("Identifiant:");
+        if self.show_duplicates {
+            self.show_duplicates_window(ctx);
+        }
+
+        if self.show_palette {
+            self.show_command_palette(ctx);
+        }
+
+        if self.show_health {
+            self.show_health_window(ctx);
+        } else {
+            self.strength_histogram = None;
+        }
+
+        if self.show_reencrypt {
+            self.show_reencrypt_window(ctx);
+        }
+
+        if self.show_save_as {
+            self.show_save_as_window(ctx);
+        }
+
+        if self.show_folders {
+            self.show_folders_window(ctx);
+        }
+
+        if self.show_batch_open {
+            self.show_batch_open_window(ctx);
+        }
+
+        if self.show_key_shares {
+            self.show_key_shares_window(ctx);
+        }
+
+        if let Some(kind) = self.confirm_export_plain {
+            self.show_export_plain_confirmation(ctx, kind);
+        }
+
+        if self.confirm_replace_import {
+            self.show_replace_import_confirmation(ctx);
+        }
+    }
+
+    /// Une fenêtre est-elle ouverte par-dessus la liste? Elle garde alors le clavier pour elle.
+    fn any_window_open(&self) -> bool {
+        self.show_entry_modal
+            || self.show_generator
+            || self.show_batch_create
+            || self.show_csv_paste
+            || self.confirm_delete.is_some()
+            || self.show_audit
+            || self.show_decoy_setup
+            || self.show_settings
+            || self.show_backup
+            || self.show_csv_export
+            || self.show_duplicates
+            || self.show_palette
+            || self.show_health
+            || self.show_reencrypt
+            || self.show_save_as
+            || self.show_folders
+            || self.show_batch_open
+            || self.show_key_shares
+            || self.confirm_export_plain.is_some()
+            || self.confirm_replace_import
+    }
+
+    /// Entrées dans l'ordre affiché: celui du tableau en vue tableau, sinon celui de la liste
+    fn displayed_order(&self) -> Vec<Uuid> {
+        let Some(vault) = self.vault.as_ref().filter(|_| self.table_view) else {
+            return self.filtered_entries.clone();
+        };
+
+        let mut entries: Vec<&Entry> = self.filtered_entries.iter().filter_map(|id| vault.get_entry(*id)).collect();
+        entries.sort_by(|a, b| {
+            let ordering = match self.table_sort {
+                TableColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                TableColumn::Login => a.login.to_lowercase().cmp(&b.login.to_lowercase()),
+            };
+            if self.table_sort_ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+        entries.iter().map(|e| e.id).collect()
+    }
+
+    /// ↑/↓, Début/Fin et Entrée (modifier) sur la liste, quand aucun champ de saisie n'a le focus
+    /// et qu'aucune fenêtre n'est ouverte
+    fn handle_list_keys(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() || self.any_window_open() {
+            return;
+        }
+        let order = self.displayed_order();
+        let Some(last) = order.len().checked_sub(1) else {
+            return;
+        };
+
+        let (up, down, home, end, enter) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Home),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::End),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+            )
+        });
+
+        let current = self.selected_entry.and_then(|id| order.iter().position(|e| *e == id));
+        let target = if home {
+            Some(0)
+        } else if end {
+            Some(last)
+        } else if down {
+            Some(current.map_or(0, |i| (i + 1).min(last)))
+        } else if up {
+            Some(current.map_or(last, |i| i.saturating_sub(1)))
+        } else {
+            None
+        };
+
+        if let Some(i) = target {
+            self.selected_entry = Some(order[i]);
+            self.scroll_to_selected = true;
+        }
+
+        if enter {
+            if let Some(i) = current {
+                self.apply_entry_action(ctx, EntryAction::Edit, order[i]);
+            }
+        }
+    }
+
+    /// Vue tableau: une ligne par entrée filtrée, avec copie directe
+    fn show_entry_table(&mut self, ui: &mut egui::Ui) {
+        let Some(vault) = &self.vault else {
+            return;
+        };
+
+        let entries: Vec<&Entry> = self.displayed_order().iter().filter_map(|id| vault.get_entry(*id)).collect();
+
+        let can_copy = self.clipboard.is_available();
+        let mut sort_clicked = None;
+        let mut open_details = None;
+        let mut row_action: Option<(EntryAction, Uuid)> = None;
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("entry_table").striped(true).num_columns(3).show(ui, |ui| {
+                for (column, title) in [(TableColumn::Name, "Nom"), (TableColumn::Login, "Identifiant")] {
+                    let arrow = match (self.table_sort == column, self.table_sort_ascending) {
+                        (false, _) => "",
+                        (true, true) => " ⏶",
+                        (true, false) => " ⏷",
+                    };
+                    let header = egui::RichText::new(format!("{}{}", title, arrow)).strong();
+                    if ui.add(egui::Button::new(header).frame(false)).clicked() {
+                        sort_clicked = Some(column);
+                    }
+                }
+                ui.strong("Actions");
+                ui.end_row();
+
+                for entry in &entries {
+                    let label = format!("{} {}", entry.display_icon(), entry.name);
+                    let is_selected = self.selected_entry == Some(entry.id);
+                    let response = ui.selectable_label(is_selected, label);
+                    if is_selected && self.scroll_to_selected {
+                        response.scroll_to_me(None);
+                    }
+                    if response.clicked() {
+                        open_details = Some(entry.id);
+                    }
+                    ui.label(&entry.login);
+                    ui.horizontal(|ui| {
+                        if !entry.is_secure_note() {
+                            if ui
+                                .add_enabled(can_copy, egui::Button::new("🔑"))
+                                .on_hover_text("Copier le mot de passe")
+                                .on_disabled_hover_text(CLIPBOARD_UNAVAILABLE)
+                                .clicked()
+                            {
+                                row_action = Some((EntryAction::CopyPassword, entry.id));
+                            }
+                            if ui
+                                .add_enabled(can_copy, egui::Button::new("👤"))
+                                .on_hover_text("Copier l'identifiant")
+                                .on_disabled_hover_text(CLIPBOARD_UNAVAILABLE)
+                                .clicked()
+                            {
+                                row_action = Some((EntryAction::CopyLogin, entry.id));
+                            }
+                        }
+                        if ui.button("✏️").on_hover_text("Modifier").clicked() {
+                            row_action = Some((EntryAction::Edit, entry.id));
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+        });
+
+        self.scroll_to_selected = false;
+
+        if let Some(column) = sort_clicked {
+            // Un second clic sur la même colonne inverse l'ordre
+            self.table_sort_ascending = self.table_sort != column || !self.table_sort_ascending;
+            self.table_sort = column;
+        }
+        if let Some(id) = open_details {
+            self.selected_entry = Some(id);
+            self.table_view = false;
+        }
+        if let Some((action, entry_id)) = row_action {
+            self.apply_entry_action(ui.ctx(), action, entry_id);
+        }
+    }
+
+    fn password_age(&self, entry: &Entry) -> PasswordAge {
+        PasswordAge::from_days(
+            entry.age_days(),
+            self.settings.age_warning_days,
+            self.settings.age_critical_days,
+        )
+    }
+
+    fn apply_entry_action(&mut self, ctx: &egui::Context, action: EntryAction, entry_id: Uuid) {
+        let Some(entry) = self.vault.as_ref().and_then(|v| v.get_entry(entry_id)).cloned() else {
+            return;
+        };
+
+        match action {
+            EntryAction::CopyPassword => {
+                self.copy_password_to_clipboard(&entry.password, entry.clipboard_clear_delay);
+                self.record_use(entry_id);
+            }
+            EntryAction::CopyLogin => self.copy_to_clipboard(&entry.login),
+            EntryAction::OpenUrl => {
+                if let Some(url) = entry.link_url() {
+                    ctx.open_url(egui::OpenUrl::new_tab(url));
+                }
+            }
+            EntryAction::RotatePassword => {
+                // Passe par l'éditeur: l'ancien mot de passe reste enregistré tant que le nouveau
+                // n'a pas été changé sur le site puis sauvegardé
+                match generate_password(entry.generator_options(&self.generator_options)) {
+                    Ok(password) => {
+                        let mut draft = entry.clone();
+                        draft.password.zeroize();
+                        draft.password = password;
+                        self.open_entry_editor(draft);
+                        self.editor_show_password = true;
+                    }
+                    Err(e) => self.error_message = Some(e),
+                }
+            }
+            EntryAction::Edit => {
+                self.open_entry_editor(entry);
+            }
+            EntryAction::Duplicate => {
+                let mut copy = Entry::new(
+                    format!("{} (copie)", entry.name),
+                    entry.login.clone(),
+                    entry.password.clone(),
+                );
+                copy.kind = entry.kind;
+                copy.login_label = entry.login_label.clone();
+                copy.urls = entry.urls.clone();
+                copy.notes = entry.notes.clone();
+                copy.sealed_notes = entry.sealed_notes.clone();
+                copy.tags = entry.tags.clone();
+                copy.folder = entry.folder.clone();
+                copy.icon = entry.icon.clone();
+                copy.clipboard_clear_delay = entry.clipboard_clear_delay;
+                copy.linked_entries = entry.linked_entries.clone();
+                copy.password_policy = entry.password_policy.clone();
+                let name = copy.name.clone();
+                let copy_id = copy.id;
+                if let Some(vault) = &mut self.vault {
+                    vault.add_entry(copy);
+                }
+                self.add_audit(AuditAction::EntryCreated(name));
+                self.selected_entry = Some(copy_id);
+                self.update_search();
+                self.mark_dirty();
+            }
+            EntryAction::Delete => {
+                self.confirm_delete = Some(entry_id);
+            }
+        }
+    }
+
+    fn detail_matches(&self, text: &str) -> bool {
+        !self.detail_filter.is_empty() && text.to_lowercase().contains(&self.detail_filter.to_lowercase())
+    }
+
+    /// Texte surligné lorsqu'il correspond au filtre de la vue détaillée
+    fn highlighted(&self, text: &str) -> egui::RichText {
+        let rich = egui::RichText::new(text);
+        if self.detail_matches(text) {
+            rich.background_color(egui::Color32::from_rgb(255, 193, 7)).color(egui::Color32::BLACK)
+        } else {
+            rich
+        }
+    }
+
+    /// Fait défiler jusqu'à la première correspondance après une saisie dans le filtre
+    fn scroll_if_match(&mut self, response: &egui::Response, text: &str) {
+        if self.detail_scroll_pending && self.detail_matches(text) {
+            response.scroll_to_me(Some(egui::Align::Center));
+            self.detail_scroll_pending = false;
+        }
+    }
+
+    fn detail_label(&mut self, ui: &mut egui::Ui, text: &str) -> egui::Response {
+        let response = ui.label(self.highlighted(text));
+        self.scroll_if_match(&response, text);
+        response
+    }
+
+    fn hide_sealed_notes(&mut self) {
+        if let Some((_, notes)) = &mut self.revealed_notes {
+            notes.zeroize();
+        }
+        self.revealed_notes = None;
+        self.sealed_notes_password.zeroize();
+    }
+
+    fn reveal_sealed_notes(&mut self, entry: &Entry) {
+        match open_sealed_notes(entry, &self.sealed_notes_password) {
+            Ok(notes) => {
+                self.hide_sealed_notes();
+                self.revealed_notes = Some((entry.id, notes));
+            }
+            Err(e) => self.error_message = Some(e.to_string()),
+        }
+        self.sealed_notes_password.zeroize();
+    }
+
+    /// Notes protégées par le second mot de passe de l'entrée
+    fn show_sealed_notes(&mut self, ui: &mut egui::Ui, entry: &Entry) {
+        if entry.sealed_notes.is_none() {
+            return;
+        }
+
+        ui.add_space(10.0);
+        ui.group(|ui| {
+            self.detail_label(ui, "🔒 Notes protégées:");
+            let revealed = self.revealed_notes.as_ref().filter(|(id, _)| *id == entry.id).map(|(_, n)| n.clone());
+            match revealed {
+                Some(mut notes) => {
+                    self.detail_label(ui, &notes);
+                    notes.zeroize();
+                    if ui.button("🙈 Masquer").clicked() {
+                        self.hide_sealed_notes();
+                    }
+                }
+                None => {
+                    ui.horizontal(|ui| {
+                        let response = ui.add(
+                            egui::TextEdit::singleline(&mut self.sealed_notes_password)
+                                .password(true)
+                                .hint_text("Mot de passe des notes"),
+                        );
+                        let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                        if ui.button("🔓 Révéler").clicked() || submitted {
+                            self.reveal_sealed_notes(entry);
+                        }
+                    });
+                }
+            }
+        });
+    }
+
+    fn show_entry_details(&mut self, ui: &mut egui::Ui, entry: &Entry) {
+        ui.heading(format!("{} {}", entry.display_icon(), entry.name));
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            if ui.button("✏️ Modifier").clicked() {
+                self.open_entry_editor(entry.clone());
+            }
+
+            if ui.button("🗑️ Supprimer").clicked() {
+                self.confirm_delete = Some(entry.id);
+            }
+
+            if !entry.is_secure_note() && !self.settings.copy_templates.is_empty() {
+                let mut chosen = None;
+                ui.add_enabled_ui(self.clipboard.is_available(), |ui| {
+                    ui.menu_button("📋 Copier au format…", |ui| {
+                        for (i, template) in self.settings.copy_templates.iter().enumerate() {
+                            if ui.button(&template.name).on_hover_text(&template.template).clicked() {
+                                chosen = Some(i);
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                });
+                let formatted = chosen
+                    .and_then(|i| self.settings.copy_templates.get(i))
+                    .map(|t| format_entry(entry, &t.template, t.escape));
+                if let Some(mut text) = formatted {
+                    self.copy_password_to_clipboard(&text, entry.clipboard_clear_delay);
+                    self.record_use(entry.id);
+                    text.zeroize();
+                }
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.horizontal(|ui| {
+            ui.label("🔎");
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.detail_filter).hint_text("Rechercher dans l'entrée..."),
+            );
+            if response.changed() {
+                self.detail_scroll_pending = true;
+            }
+        });
+
+        ui.add_space(10.0);
+
+        if entry.is_secure_note() {
+            ui.group(|ui| {
+                self.detail_label(ui, "Note:");
+                self.detail_label(ui, entry.notes.as_deref().unwrap_or(""));
+            });
+            self.show_sealed_notes(ui, entry);
+
+            self.show_linked_entries(ui, entry);
+
+            ui.add_space(10.0);
+            ui.label(format!("Créé: {}", self.settings.format_timestamp(&entry.created_at)));
+            ui.label(format!("Modifié: {}", self.settings.format_timestamp(&entry.modified_at)));
+            return;
+        }
+
+        ui.group(|ui| {
+            self.detail_label(ui, &format!("{}:", entry.login_label()));
             ui.horizontal(|ui| {
-                ui.label(&entry.login);
-                if ui.button("📋").clicked() {
+                self.detail_label(ui, &entry.login);
+                if ui
+                    .add_enabled(self.clipboard.is_available(), egui::Button::new("📋"))
+                    .on_disabled_hover_text(CLIPBOARD_UNAVAILABLE)
+                    .clicked()
+                {
                     let login = entry.login.clone();
                     self.copy_to_clipboard(&login);
-                    self.update_activity();
                 }
             });
         });
@@ -421,250 +2428,2629 @@ impl PasswordManagerApp {
         ui.group(|ui| {
             ui.label("Mot de passe:");
             ui.horizontal(|ui| {
-                ui.label("••••••••");
-                if ui.button("📋 Copier").clicked() {
+                if self.is_revealing_all() {
+                    ui.add(egui::TextEdit::singleline(&mut entry.password.as_str()).font(egui::TextStyle::Monospace));
+                } else if self.revealed_password == Some(entry.id) {
+                    // Lecture seule mais sélectionnable, pour une copie manuelle
+                    ui.add(egui::TextEdit::singleline(&mut entry.password.as_str()).font(egui::TextStyle::Monospace));
+                    if ui.button("🙈 Masquer").clicked() {
+                        self.revealed_password = None;
+                    }
+                } else {
+                    ui.label("••••••••");
+                }
+                if ui
+                    .add_enabled(self.clipboard.is_available(), egui::Button::new("📋 Copier"))
+                    .on_disabled_hover_text(CLIPBOARD_UNAVAILABLE)
+                    .clicked()
+                {
                     let password = entry.password.clone();
-                    self.copy_to_clipboard(&password);
-                    self.update_activity();
+                    self.copy_password_to_clipboard(&password, entry.clipboard_clear_delay);
+                    self.record_use(entry.id);
                 }
             });
+            if !self.clipboard.is_available() {
+                ui.horizontal(|ui| {
+                    ui.weak(CLIPBOARD_UNAVAILABLE);
+                    if !self.is_revealing_all()
+                        && self.revealed_password != Some(entry.id)
+                        && ui.button("👁 Afficher pour copier").clicked()
+                    {
+                        self.revealed_password = Some(entry.id);
+                        self.record_use(entry.id);
+                    }
+                    if ui.button("🔄 Réessayer").clicked() {
+                        self.retry_clipboard();
+                    }
+                });
+            }
+            if entry.use_count > 0 {
+                ui.weak(format!("Copié {} fois", entry.use_count));
+            }
+            ui.colored_label(
+                self.password_age(entry).color(),
+                format!("🕒 Modifié il y a {} jours", entry.age_days()),
+            );
+            if self.master_reused_by.contains(&entry.id) {
+                ui.colored_label(
+                    egui::Color32::from_rgb(220, 53, 69),
+                    "⚠️ Identique au mot de passe maître: changez-le",
+                );
+            }
+            if let Some(status) = &entry.breach_status {
+                let checked = self.settings.format_timestamp(&status.checked_at);
+                if status.is_breached() {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(220, 53, 69),
+                        format!("🚨 Présent {} fois dans des fuites (vérifié le {})", status.count, checked),
+                    );
+                } else {
+                    ui.weak(format!("✓ Absent des fuites connues (vérifié le {})", checked));
+                }
+            }
         });
 
-        if let Some(url) = &entry.url {
+        if entry.totp.is_some() {
+            ui.add_space(10.0);
+            ui.group(|ui| {
+                self.show_totp_section(ui, entry);
+            });
+        }
+
+        if !entry.urls.is_empty() {
+            ui.add_space(10.0);
+            ui.group(|ui| {
+                self.detail_label(ui, "URL:");
+                for url in &entry.urls {
+                    let link = clickable_url(url);
+                    ui.horizontal(|ui| {
+                        let response = match &link {
+                            Some(link) => ui.add(egui::Hyperlink::from_label_and_url(self.highlighted(url), link)),
+                            // Schéma inconnu ou dangereux (file:, javascript:…): texte seul
+                            None => ui.label(self.highlighted(url)),
+                        };
+                        self.scroll_if_match(&response, url);
+                        if ui
+                            .add_enabled(link.is_some(), egui::Button::new("🌐"))
+                            .on_hover_text("Ouvrir")
+                            .clicked()
+                        {
+                            if let Some(link) = &link {
+                                ui.ctx().open_url(egui::OpenUrl::new_tab(link));
+                            }
+                        }
+                        if ui
+                            .add_enabled(self.clipboard.is_available(), egui::Button::new("📋"))
+                            .on_hover_text("Copier l'URL")
+                            .clicked()
+                        {
+                            self.copy_to_clipboard(url);
+                        }
+                    });
+                    if link.is_none() {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(255, 193, 7),
+                            "⚠️ Lien désactivé: seuls http, https et ftp sont ouverts",
+                        );
+                    }
+                }
+
+                let credential_url = entry.credential_url();
+                let button = ui
+                    .add_enabled(
+                        credential_url.is_some() && self.clipboard.is_available(),
+                        egui::Button::new("🔗 Copier l'URL avec identifiants"),
+                    )
+                    .on_hover_text("⚠️ Le mot de passe figure en clair dans l'URL copiée")
+                    .on_disabled_hover_text("Nécessite une URL valide, un identifiant et le presse-papiers");
+                if button.clicked() {
+                    if let Some(mut credential_url) = credential_url {
+                        self.copy_password_to_clipboard(&credential_url, entry.clipboard_clear_delay);
+                        self.record_use(entry.id);
+                        credential_url.zeroize();
+                    }
+                }
+            });
+        }
+
+        if let Some(notes) = &entry.notes {
+            ui.add_space(10.0);
+            ui.group(|ui| {
+                self.detail_label(ui, "Notes:");
+                self.detail_label(ui, notes);
+            });
+        }
+        self.show_sealed_notes(ui, entry);
+
+        if let Some(folder) = &entry.folder {
+            ui.add_space(10.0);
+            self.detail_label(ui, &format!("📁 {}", folder));
+        }
+
+        if !entry.tags.is_empty() {
             ui.add_space(10.0);
             ui.group(|ui| {
-                ui.label("URL:");
-                ui.hyperlink(url);
+                self.detail_label(ui, "Tags:");
+                ui.horizontal_wrapped(|ui| {
+                    for tag in &entry.tags {
+                        self.detail_label(ui, &format!("🏷️ {}", tag));
+                    }
+                });
             });
         }
 
-        if let Some(notes) = &entry.notes {
-            ui.add_space(10.0);
-            ui.group(|ui| {
-                ui.label("Notes:");
-                ui.label(notes);
-            });
+        self.show_linked_entries(ui, entry);
+
+        ui.add_space(10.0);
+        ui.label(format!("Créé: {}", self.settings.format_timestamp(&entry.created_at)));
+        ui.label(format!("Modifié: {}", self.settings.format_timestamp(&entry.modified_at)));
+    }
+
+    fn show_totp_section(&mut self, ui: &mut egui::Ui, entry: &Entry) {
+        let Some(totp) = &entry.totp else {
+            return;
+        };
+
+        let now = chrono::Utc::now().timestamp();
+        ui.ctx().request_repaint_after(COUNTDOWN_REPAINT);
+        ui.label("Code TOTP:");
+        ui.horizontal(|ui| {
+            match totp.code_at(now) {
+                Some(code) => {
+                    ui.monospace(egui::RichText::new(&code).size(18.0));
+                    ui.weak(format!("{}s", totp.remaining_secs(now)));
+                    if ui
+                        .add_enabled(self.clipboard.is_available(), egui::Button::new("📋"))
+                        .on_disabled_hover_text(CLIPBOARD_UNAVAILABLE)
+                        .clicked()
+                    {
+                        self.copy_to_clipboard(&code);
+                    }
+                }
+                None => {
+                    ui.colored_label(egui::Color32::from_rgb(220, 53, 69), "Secret TOTP invalide");
+                }
+            }
+        });
+
+        ui.collapsing("🕒 Codes refusés ? Décalage d'horloge", |ui| {
+            ui.weak("Essayez les codes des périodes voisines:");
+            for (code, offset) in entry.totp_window(1) {
+                let label = match offset {
+                    -1 => "Précédent",
+                    0 => "Courant",
+                    _ => "Suivant",
+                };
+                ui.horizontal(|ui| {
+                    ui.label(format!("{}:", label));
+                    ui.monospace(code);
+                });
+            }
+
+            if self.clock_looks_behind() {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 193, 7),
+                    "⚠️ L'horloge système semble en retard: le coffre a été modifié « dans le futur »",
+                );
+            }
+        });
+    }
+
+    /// Une modification datée du futur trahit une horloge système en retard
+    fn clock_looks_behind(&self) -> bool {
+        let tolerance = chrono::Utc::now() + chrono::Duration::minutes(5);
+        self.vault.as_ref().map_or(false, |vault| {
+            vault.modified_at > tolerance || vault.entries.iter().any(|e| e.modified_at > tolerance)
+        })
+    }
+
+    fn show_linked_entries(&mut self, ui: &mut egui::Ui, entry: &Entry) {
+        if entry.linked_entries.is_empty() {
+            return;
+        }
+
+        ui.add_space(10.0);
+        ui.group(|ui| {
+            self.detail_label(ui, "🔗 Entrées liées:");
+            for linked_id in &entry.linked_entries {
+                let linked = self
+                    .vault
+                    .as_ref()
+                    .and_then(|v| v.get_entry(*linked_id))
+                    .map(|e| format!("{} {}", e.display_icon(), e.name));
+                match linked {
+                    Some(label) => {
+                        if ui.link(self.highlighted(&label)).clicked() {
+                            self.selected_entry = Some(*linked_id);
+                        }
+                    }
+                    None => {
+                        ui.weak("(entrée supprimée)");
+                    }
+                }
+            }
+        });
+    }
+
+    fn open_entry_editor(&mut self, entry: Entry) {
+        self.totp_input = entry.totp.as_ref().map(|t| t.secret.clone()).unwrap_or_default();
+        self.tag_input.clear();
+        self.editing_entry = Some(entry);
+        self.show_entry_modal = true;
+    }
+
+    fn show_entry_editor(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut should_close = false;
+        let mut should_save = false;
+        let mut save_anyway = false;
+        let mut merge_requested = false;
+        let mut error_msg = None;
+        let known_tags = self.vault.as_ref().map(|v| v.all_tags()).unwrap_or_default();
+        let known_folders = self.vault.as_ref().map(|v| v.folders()).unwrap_or_default();
+
+        egui::Window::new("Éditer l'entrée")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                if let Some(entry) = &mut self.editing_entry {
+                    ui.horizontal(|ui| {
+                        for kind in [EntryKind::Login, EntryKind::SecureNote] {
+                            ui.radio_value(&mut entry.kind, kind, kind.label());
+                        }
+                    });
+
+                    ui.add_space(10.0);
+                    ui.label(if entry.is_secure_note() { "Titre:" } else { "Nom du service:" });
+                    ui.text_edit_singleline(&mut entry.name);
+
+                    ui.add_space(10.0);
+                    ui.label("Icône:");
+                    ui.horizontal_wrapped(|ui| {
+                        for icon in ENTRY_ICONS {
+                            let is_selected = entry.display_icon() == *icon;
+                            if ui.selectable_label(is_selected, *icon).clicked() {
+                                entry.icon = Some(icon.to_string());
+                            }
+                        }
+                    });
+
+                    if !entry.is_secure_note() {
+                        ui.add_space(10.0);
+                        ui.horizontal(|ui| {
+                            ui.label(format!("{}:", entry.login_label()));
+                            let mut label = entry.login_label.clone().unwrap_or_default();
+                            ui.add(
+                                egui::TextEdit::singleline(&mut label)
+                                    .hint_text(DEFAULT_LOGIN_LABEL)
+                                    .desired_width(120.0),
+                            )
+                            .on_hover_text("Nom du champ: n° de compte, IBAN, e-mail…");
+                            entry.login_label = if label.is_empty() { None } else { Some(label) };
+                        });
+                        ui.horizontal(|ui| {
+                            ui.text_edit_singleline(&mut entry.login);
+                            ui.menu_button("🎲", |ui| {
+                                for style in [UsernameStyle::Random, UsernameStyle::Words] {
+                                    if ui.button(style.label()).clicked() {
+                                        entry.login = generate_username(style);
+                                        ui.close_menu();
+                                    }
+                                }
+                            })
+                            .response
+                            .on_hover_text("Générer un identifiant");
+                        });
+
+                        ui.add_space(10.0);
+                        ui.label("Mot de passe:");
+                        ui.horizontal(|ui| {
+                            ui.add(egui::TextEdit::singleline(&mut entry.password).password(!self.editor_show_password));
+                            let (icon, hint) = if self.editor_show_password {
+                                ("🙈", "Masquer")
+                            } else {
+                                ("👁", "Afficher")
+                            };
+                            if ui.button(icon).on_hover_text(hint).clicked() {
+                                self.editor_show_password = !self.editor_show_password;
+                            }
+                            if ui.button("⚡").on_hover_text("Générer selon les règles du site").clicked() {
+                                match generate_password(entry.generator_options(&self.generator_options)) {
+                                    Ok(pwd) => entry.password = pwd,
+                                    Err(e) => error_msg = Some(e),
+                                }
+                            }
+                            if ui.button("🎲 Générer").clicked() {
+                                self.show_generator = true;
+                            }
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label("Règles du site:");
+                            let selected = match &entry.password_policy {
+                                None => "Réglages du générateur".to_string(),
+                                Some(policy) => self
+                                    .settings
+                                    .generator_presets
+                                    .iter()
+                                    .find(|(_, options)| options == policy)
+                                    .map_or("Personnalisées".to_string(), |(name, _)| name.clone()),
+                            };
+                            egui::ComboBox::from_id_source("entry_password_policy")
+                                .selected_text(selected)
+                                .show_ui(ui, |ui| {
+                                    if ui
+                                        .selectable_label(entry.password_policy.is_none(), "Réglages du générateur")
+                                        .clicked()
+                                    {
+                                        entry.password_policy = None;
+                                    }
+                                    for (name, options) in &self.settings.generator_presets {
+                                        let is_selected = entry.password_policy.as_ref() == Some(options);
+                                        if ui.selectable_label(is_selected, name).clicked() {
+                                            entry.password_policy = Some(options.clone());
+                                        }
+                                    }
+                                    if ui.button("Copier les réglages actuels").clicked() {
+                                        entry.password_policy = Some(self.generator_options.clone());
+                                    }
+                                })
+                                .response
+                                .on_hover_text("Appliquées à chaque renouvellement du mot de passe de cette entrée");
+                        });
+
+                        let mut custom_delay = entry.clipboard_clear_delay.is_some();
+                        ui.horizontal(|ui| {
+                            if ui.checkbox(&mut custom_delay, "Effacement du presse-papiers après").changed() {
+                                entry.clipboard_clear_delay = if custom_delay { Some(10) } else { None };
+                            }
+                            if let Some(delay) = &mut entry.clipboard_clear_delay {
+                                ui.add(egui::DragValue::new(delay).range(1..=300).suffix(" s"));
+                            }
+                        });
+
+                        ui.add_space(10.0);
+                        ui.label("TOTP (secret base32 ou URI otpauth://, optionnel):");
+                        ui.add(egui::TextEdit::singleline(&mut self.totp_input).password(true));
+
+                        ui.add_space(10.0);
+                        ui.label("URL (optionnel, la première sert aux liens):");
+                        // Toujours une ligne de saisie; les lignes vides sont retirées à l'enregistrement
+                        if entry.urls.is_empty() {
+                            entry.urls.push(String::new());
+                        }
+                        let mut removed_url = None;
+                        for (i, url) in entry.urls.iter_mut().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.text_edit_singleline(url);
+                                if ui.small_button("❌").on_hover_text("Retirer cette URL").clicked() {
+                                    removed_url = Some(i);
+                                }
+                            });
+                        }
+                        if let Some(i) = removed_url {
+                            entry.urls.remove(i);
+                        }
+                        if ui.small_button("➕ Ajouter une URL").clicked() {
+                            entry.urls.push(String::new());
+                        }
+                    }
+
+                    ui.add_space(10.0);
+                    ui.label("Dossier (optionnel):");
+                    ui.horizontal(|ui| {
+                        let mut folder = entry.folder.clone().unwrap_or_default();
+                        ui.add(egui::TextEdit::singleline(&mut folder).hint_text("Aucun"));
+                        if !known_folders.is_empty() {
+                            ui.menu_button("📁", |ui| {
+                                for known in &known_folders {
+                                    if ui.button(known).clicked() {
+                                        folder = known.clone();
+                                        ui.close_menu();
+                                    }
+                                }
+                            })
+                            .response
+                            .on_hover_text("Dossiers existants");
+                        }
+                        entry.folder = if folder.is_empty() { None } else { Some(folder) };
+                    });
+
+                    ui.add_space(10.0);
+                    ui.label("Tags:");
+                    let mut removed_tag = None;
+                    ui.horizontal_wrapped(|ui| {
+                        for (i, tag) in entry.tags.iter().enumerate() {
+                            if ui.small_button(format!("🏷️ {} ❌", tag)).on_hover_text("Retirer").clicked() {
+                                removed_tag = Some(i);
+                            }
+                        }
+                    });
+                    if let Some(i) = removed_tag {
+                        entry.tags.remove(i);
+                    }
+
+                    let mut added_tag = None;
+                    ui.horizontal(|ui| {
+                        let response = ui.add(egui::TextEdit::singleline(&mut self.tag_input).hint_text("Nouveau tag"));
+                        let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                        if (ui.button("➕").clicked() || submitted) && !self.tag_input.trim().is_empty() {
+                            added_tag = Some(self.tag_input.clone());
+                        }
+                    });
+                    // Suggestions parmi les tags existants, pour éviter "work" / "Work" / "travail"
+                    let typed = self.tag_input.trim().to_lowercase();
+                    if !typed.is_empty() {
+                        ui.horizontal_wrapped(|ui| {
+                            let suggestions = known_tags.iter().filter(|t| {
+                                t.to_lowercase().contains(&typed)
+                                    && !entry.tags.iter().any(|e| e.to_lowercase() == t.to_lowercase())
+                            });
+                            for tag in suggestions.take(8) {
+                                if ui.small_button(tag).clicked() {
+                                    added_tag = Some(tag.clone());
+                                }
+                            }
+                        });
+                    }
+                    if let Some(tag) = added_tag {
+                        let tag = self
+                            .vault
+                            .as_ref()
+                            .map_or_else(|| tag.trim().to_string(), |v| v.canonical_tag(&tag));
+                        if !entry.tags.iter().any(|t| t.to_lowercase() == tag.to_lowercase()) {
+                            entry.tags.push(tag);
+                        }
+                        self.tag_input.clear();
+                    }
+
+                    ui.add_space(10.0);
+                    ui.label(if entry.is_secure_note() { "Note:" } else { "Notes (optionnel):" });
+                    if entry.sealed_notes.is_some() {
+                        ui.weak("🔒 Notes protégées par un second mot de passe");
+                        ui.horizontal(|ui| {
+                            ui.add(
+                                egui::TextEdit::singleline(&mut self.sealed_notes_password)
+                                    .password(true)
+                                    .hint_text("Mot de passe des notes"),
+                            );
+                            if ui.button("🔓 Retirer la protection").clicked() {
+                                if let Err(e) = remove_notes_seal(entry, &self.sealed_notes_password) {
+                                    self.error_message = Some(e.to_string());
+                                }
+                                self.sealed_notes_password.zeroize();
+                            }
+                        });
+                    } else {
+                        let mut notes = entry.notes.clone().unwrap_or_default();
+                        ui.text_edit_multiline(&mut notes);
+                        entry.notes = if notes.is_empty() { None } else { Some(notes) };
+                        ui.horizontal(|ui| {
+                            ui.add(
+                                egui::TextEdit::singleline(&mut self.sealed_notes_password)
+                                    .password(true)
+                                    .hint_text("Second mot de passe"),
+                            );
+                            let can_seal = entry.notes.is_some() && self.sealed_notes_password.len() >= 8;
+                            if ui
+                                .add_enabled(can_seal, egui::Button::new("🔐 Protéger les notes"))
+                                .on_disabled_hover_text("Notes non vides et mot de passe d'au moins 8 caractères")
+                                .clicked()
+                            {
+                                if let Err(e) = seal_notes(entry, &self.sealed_notes_password) {
+                                    self.error_message = Some(e.to_string());
+                                }
+                                self.sealed_notes_password.zeroize();
+                            }
+                        });
+                    }
+
+                    ui.add_space(10.0);
+                    ui.label("Entrées liées:");
+                    let names: Vec<(Uuid, String)> = self
+                        .vault
+                        .as_ref()
+                        .map(|v| v.entries.iter().map(|e| (e.id, e.name.clone())).collect())
+                        .unwrap_or_default();
+                    let name_of = |id: &Uuid| {
+                        names
+                            .iter()
+                            .find(|(other, _)| other == id)
+                            .map_or("(entrée supprimée)".to_string(), |(_, name)| name.clone())
+                    };
+                    let mut unlink = None;
+                    for linked_id in &entry.linked_entries {
+                        ui.horizontal(|ui| {
+                            ui.label(format!("🔗 {}", name_of(linked_id)));
+                            if ui.small_button("❌").clicked() {
+                                unlink = Some(*linked_id);
+                            }
+                        });
+                    }
+                    if let Some(id) = unlink {
+                        entry.linked_entries.retain(|linked| *linked != id);
+                    }
+                    egui::ComboBox::from_id_source("link_entry")
+                        .selected_text("Lier une entrée...")
+                        .show_ui(ui, |ui| {
+                            for (id, name) in &names {
+                                if *id == entry.id || entry.linked_entries.contains(id) {
+                                    continue;
+                                }
+                                if ui.selectable_label(false, name).clicked() {
+                                    entry.linked_entries.push(*id);
+                                }
+                            }
+                        });
+
+                    if self.duplicate_name_warning {
+                        ui.add_space(10.0);
+                        ui.colored_label(
+                            egui::Color32::from_rgb(255, 193, 7),
+                            format!("⚠️ Une autre entrée s'appelle déjà « {} »", entry.name.trim()),
+                        );
+                        ui.horizontal(|ui| {
+                            if ui.button("Enregistrer quand même").clicked() {
+                                save_anyway = true;
+                            }
+                            if ui.button("✏️ Renommer").clicked() {
+                                self.duplicate_name_warning = false;
+                            }
+                            if ui
+                                .button("🔀 Fusionner")
+                                .on_hover_text("Reporte ces informations sur l'entrée existante")
+                                .clicked()
+                            {
+                                merge_requested = true;
+                            }
+                        });
+                    }
+
+                    ui.add_space(20.0);
+
+                    ui.horizontal(|ui| {
+                        if ui.button("💾 Sauvegarder").clicked() {
+                            entry.normalize();
+                            if entry.is_secure_note() && entry.name.is_empty() {
+                                error_msg = Some("Titre requis".to_string());
+                            } else if !entry.is_secure_note()
+                                && (entry.name.is_empty() || entry.login.is_empty() || entry.password.is_empty())
+                            {
+                                error_msg = Some("Nom, login et mot de passe requis".to_string());
+                            } else if self.totp_input.trim().is_empty() {
+                                entry.totp = None;
+                                should_save = true;
+                                should_close = true;
+                            } else {
+                                // Conserver les chiffres/période d'une URI si le secret n'a pas changé
+                                let unchanged = entry
+                                    .totp
+                                    .as_ref()
+                                    .map_or(false, |t| t.secret == self.totp_input.trim());
+                                if !unchanged {
+                                    match parse_totp(&self.totp_input) {
+                                        Ok(config) => {
+                                            entry.totp = Some(config);
+                                            should_save = true;
+                                            should_close = true;
+                                        }
+                                        Err(e) => error_msg = Some(e),
+                                    }
+                                } else {
+                                    should_save = true;
+                                    should_close = true;
+                                }
+                            }
+                        }
+
+                        if ui.button("❌ Annuler").clicked() {
+                            should_close = true;
+                        }
+                    });
+                }
+            });
+
+        if !open {
+            should_close = true;
+        }
+
+        if let Some(msg) = error_msg {
+            self.error_message = Some(msg);
+        }
+
+        // Avertissement seulement: un doublon volontaire reste possible
+        if should_save && !save_anyway && self.editing_name_is_duplicate() {
+            self.duplicate_name_warning = true;
+            should_save = false;
+            should_close = false;
+        }
+
+        if save_anyway {
+            should_save = true;
+            should_close = true;
+        }
+
+        if merge_requested {
+            self.merge_editing_into_same_name();
+            should_close = true;
+        }
+
+        if should_save {
+            if let Some(entry) = &self.editing_entry {
+                // L'éditeur garde une ligne d'URL vide à l'écran: chaque chemin d'enregistrement normalise
+                let mut entry_clone = entry.clone();
+                entry_clone.normalize();
+                let entry_id = entry.id;
+                let entry_name = entry.name.clone();
+                
+                if let Some(vault) = &mut self.vault {
+                    let is_existing = vault.get_entry(entry_id).is_some();
+                    
+                    let action = if is_existing {
+                        AuditAction::EntryUpdated(entry_name.clone())
+                    } else {
+                        AuditAction::EntryCreated(entry_name.clone())
+                    };
+
+                    vault.update_entry(entry_id, entry_clone.clone());
+                    if !vault.entries.iter().any(|e| e.id == entry_clone.id) {
+                        vault.add_entry(entry_clone);
+                    }
+
+                    self.add_audit(action);
+                    self.update_search();
+                    self.mark_dirty();
+                    self.check_master_reuse();
+                    if self.master_reused_by.contains(&entry_id) {
+                        self.error_message = Some(
+                            "⚠️ Ce mot de passe est identique au mot de passe maître".to_string(),
+                        );
+                    } else {
+                        self.notify("Entrée sauvegardée".to_string());
+                    }
+                }
+            }
+        }
+
+        if should_close {
+            self.show_entry_modal = false;
+            self.editing_entry = None;
+            self.totp_input.zeroize();
+            self.duplicate_name_warning = false;
+        }
+    }
+
+    fn editing_name_is_duplicate(&self) -> bool {
+        match (&self.vault, &self.editing_entry) {
+            (Some(vault), Some(entry)) => vault.name_exists(&entry.name, Some(entry.id)),
+            _ => false,
+        }
+    }
+
+    /// Reporte l'entrée éditée sur l'entrée existante du même nom (tags réunis),
+    /// puis retire l'entrée éditée si elle était déjà enregistrée
+    fn merge_editing_into_same_name(&mut self) {
+        let (Some(vault), Some(edited)) = (&mut self.vault, &self.editing_entry) else {
+            return;
+        };
+        let Some(target) = vault.find_by_name(&edited.name, Some(edited.id)) else {
+            return;
+        };
+
+        let mut merged = edited.clone();
+        merged.normalize();
+        merged.id = target.id;
+        merged.created_at = target.created_at;
+        for tag in &target.tags {
+            if !merged.tags.contains(tag) {
+                merged.tags.push(tag.clone());
+            }
+        }
+        for id in &target.linked_entries {
+            if !merged.linked_entries.contains(id) {
+                merged.linked_entries.push(*id);
+            }
+        }
+
+        let (target_id, edited_id, name) = (target.id, edited.id, merged.name.clone());
+        vault.update_entry(target_id, merged);
+        if vault.get_entry(edited_id).is_some() {
+            vault.delete_entry(edited_id);
+            self.add_audit(AuditAction::EntryDeleted(name.clone()));
+        }
+        if self.selected_entry == Some(edited_id) {
+            self.selected_entry = Some(target_id);
+        }
+
+        self.add_audit(AuditAction::EntryUpdated(name));
+        self.update_search();
+        self.mark_dirty();
+        self.check_master_reuse();
+        self.notify("Entrées fusionnées".to_string());
+    }
+
+    fn show_password_generator(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+
+        egui::Window::new("Générateur de mot de passe")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                self.show_generator_presets(ui);
+
+                ui.separator();
+
+                ui.label("Longueur:");
+                ui.add(egui::Slider::new(&mut self.generator_options.length, 4..=64));
+
+                ui.checkbox(&mut self.generator_options.include_uppercase, "Majuscules (A-Z)");
+                ui.checkbox(&mut self.generator_options.include_lowercase, "Minuscules (a-z)");
+                ui.checkbox(&mut self.generator_options.include_numbers, "Chiffres (0-9)");
+                ui.checkbox(&mut self.generator_options.include_symbols, "Symboles (!@#$...)");
+                symbol_preset_combo(ui, "generator_symbol_preset", &mut self.generator_options);
+                ui.checkbox(&mut self.generator_options.avoid_ambiguous, "Éviter caractères ambigus (il1Lo0O)");
+
+                ui.checkbox(&mut self.show_generator_advanced, "Avancé");
+                if self.show_generator_advanced {
+                    let options = &mut self.generator_options;
+                    egui::Grid::new("generator_bounds").show(ui, |ui| {
+                        ui.label("");
+                        ui.label("Min");
+                        ui.label("Max");
+                        ui.end_row();
+                        class_bounds_row(ui, "Majuscules", &mut options.min_uppercase, &mut options.max_uppercase);
+                        class_bounds_row(ui, "Minuscules", &mut options.min_lowercase, &mut options.max_lowercase);
+                        class_bounds_row(ui, "Chiffres", &mut options.min_digits, &mut options.max_digits);
+                        class_bounds_row(ui, "Symboles", &mut options.min_symbols, &mut options.max_symbols);
+                    });
+                }
+
+                ui.add_space(10.0);
+
+                let bits = options_entropy_bits(&self.generator_options);
+                let expected = PasswordStrength::from_entropy_bits(bits);
+                ui.horizontal(|ui| {
+                    ui.label(format!("Entropie: {:.0} bits", bits));
+                    ui.colored_label(expected.color(), expected.label());
+                });
+
+                ui.add_space(10.0);
+
+                if ui.button("🎲 Générer").clicked() {
+                    match generate_password(&self.generator_options) {
+                        Ok(pwd) => {
+                            self.generated_password = pwd;
+                        }
+                        Err(e) => {
+                            self.error_message = Some(e);
+                        }
+                    }
+                }
+
+                if !self.generated_password.is_empty() {
+                    ui.add_space(10.0);
+                    ui.label("Mot de passe généré (modifiable):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.generated_password)
+                            .font(egui::TextStyle::Monospace)
+                            .desired_width(f32::INFINITY),
+                    );
+
+                    if ui
+                        .add_enabled(self.clipboard.is_available(), egui::Button::new("📋 Copier"))
+                        .on_disabled_hover_text(CLIPBOARD_UNAVAILABLE)
+                        .clicked()
+                    {
+                        let pwd = self.generated_password.clone();
+                        self.copy_password_to_clipboard(&pwd, None);
+                    }
+
+                    if ui.button("✓ Utiliser").clicked() {
+                        if let Some(entry) = &mut self.editing_entry {
+                            entry.password = self.generated_password.clone();
+                        }
+                        self.show_generator = false;
+                    }
+
+                    let strength = estimate_strength(&self.generated_password);
+                    ui.horizontal(|ui| {
+                        ui.label("Force:");
+                        ui.colored_label(strength.color(), strength.label());
+                    });
+                }
+            });
+
+        if !open {
+            self.show_generator = false;
+        }
+
+        if !self.show_generator {
+            self.persist_generator_options();
+        }
+    }
+
+    /// Enregistre les réglages du générateur s'ils ont changé (à la fermeture, pas à chaque image)
+    fn persist_generator_options(&mut self) {
+        if self.settings.generator_options != self.generator_options {
+            self.settings.generator_options = self.generator_options.clone();
+            self.save_settings();
+        }
+    }
+
+    /// Commandes et entrées correspondant à la saisie, les meilleures en premier
+    fn palette_results(&self) -> Vec<(String, PaletteAction)> {
+        let commands = PALETTE_COMMANDS
+            .iter()
+            .map(|(label, action)| (label.to_string(), *action));
+        let entries = self.vault.iter().flat_map(|vault| {
+            vault
+                .entries
+                .iter()
+                .map(|e| (format!("{} {}", e.display_icon(), e.name), PaletteAction::SelectEntry(e.id)))
+        });
+
+        let mut scored: Vec<(i32, String, PaletteAction)> = commands
+            .chain(entries)
+            .filter_map(|(label, action)| {
+                fuzzy_score(&self.palette_query, &label).map(|score| (score, label, action))
+            })
+            .collect();
+        // Tri stable: à score égal, les commandes restent avant les entrées
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+
+        scored
+            .into_iter()
+            .take(PALETTE_MAX_RESULTS)
+            .map(|(_, label, action)| (label, action))
+            .collect()
+    }
+
+    fn show_command_palette(&mut self, ctx: &egui::Context) {
+        let results = self.palette_results();
+        self.palette_selected = self.palette_selected.min(results.len().saturating_sub(1));
+
+        let (up, down, enter, escape) = ctx.input(|i| {
+            (
+                i.key_pressed(egui::Key::ArrowUp),
+                i.key_pressed(egui::Key::ArrowDown),
+                i.key_pressed(egui::Key::Enter),
+                i.key_pressed(egui::Key::Escape),
+            )
+        });
+        if up {
+            self.palette_selected = self.palette_selected.saturating_sub(1);
+        }
+        if down && self.palette_selected + 1 < results.len() {
+            self.palette_selected += 1;
+        }
+
+        let mut chosen = None;
+
+        egui::Window::new("Palette de commandes")
+            .title_bar(false)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 60.0])
+            .fixed_size([420.0, 0.0])
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.palette_query)
+                        .hint_text("Action ou entrée...")
+                        .desired_width(f32::INFINITY),
+                );
+                response.request_focus();
+                if response.changed() {
+                    self.palette_selected = 0;
+                }
+
+                ui.separator();
+
+                if results.is_empty() {
+                    ui.weak("Aucun résultat");
+                }
+                for (i, (label, action)) in results.iter().enumerate() {
+                    if ui.selectable_label(i == self.palette_selected, label).clicked() {
+                        chosen = Some(*action);
+                    }
+                }
+            });
+
+        if enter {
+            chosen = results.get(self.palette_selected).map(|(_, action)| *action);
+        }
+
+        if let Some(action) = chosen {
+            self.show_palette = false;
+            self.run_palette_action(action);
+        } else if escape {
+            self.show_palette = false;
+        }
+    }
+
+    fn run_palette_action(&mut self, action: PaletteAction) {
+        match action {
+            PaletteAction::Lock => self.lock_vault(),
+            PaletteAction::NewEntry => {
+                self.open_entry_editor(Entry::new(String::new(), String::new(), String::new()));
+            }
+            PaletteAction::Generate => self.show_generator = true,
+            PaletteAction::ExportCsv => self.show_csv_export = true,
+            PaletteAction::Backup => self.show_backup = true,
+            PaletteAction::Settings => self.show_settings = true,
+            PaletteAction::Audit => self.show_audit = true,
+            PaletteAction::Health => self.show_health = true,
+            PaletteAction::MergeDuplicates => self.open_duplicates_tool(),
+            PaletteAction::RecopyLast => self.recopy_last(),
+            PaletteAction::BatchOpen => self.show_batch_open = true,
+            PaletteAction::SelectEntry(id) => {
+                self.selected_entry = Some(id);
+                self.detail_filter.clear();
+            }
+        }
+    }
+
+    fn open_duplicates_tool(&mut self) {
+        self.duplicate_groups = self.vault.as_ref().map(|v| v.find_exact_duplicates()).unwrap_or_default();
+        // Par défaut, on garde la première entrée de chaque groupe
+        self.duplicate_keep = self.duplicate_groups.iter().map(|group| group[0]).collect();
+        self.show_duplicates = true;
+    }
+
+    fn show_duplicates_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut should_merge = false;
+
+        egui::Window::new("🧹 Fusionner les doublons")
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(450.0)
+            .show(ctx, |ui| {
+                if self.duplicate_groups.is_empty() {
+                    ui.label("Aucun doublon exact (nom, identifiant, mot de passe et URL identiques).");
+                    return;
+                }
+
+                let Some(vault) = &self.vault else {
+                    return;
+                };
+
+                egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                    for (group, keep) in self.duplicate_groups.iter().zip(self.duplicate_keep.iter_mut()) {
+                        ui.group(|ui| {
+                            let name = vault.get_entry(group[0]).map_or("", |e| e.name.as_str());
+                            ui.strong(format!("{} ({} copies)", name, group.len()));
+                            for id in group {
+                                let Some(entry) = vault.get_entry(*id) else {
+                                    continue;
+                                };
+                                let label = format!(
+                                    "Garder celle créée le {}, copiée {} fois",
+                                    self.settings.format_timestamp(&entry.created_at),
+                                    entry.use_count
+                                );
+                                ui.radio_value(keep, *id, label);
+                            }
+                        });
+                    }
+                });
+
+                let removed: usize = self.duplicate_groups.iter().map(|group| group.len() - 1).sum();
+                ui.add_space(10.0);
+                ui.label(format!(
+                    "Aperçu: {} entrée(s) seront supprimées dans {} groupe(s).",
+                    removed,
+                    self.duplicate_groups.len()
+                ));
+
+                if ui.button("🧹 Fusionner").clicked() {
+                    should_merge = true;
+                }
+            });
+
+        if should_merge {
+            self.merge_duplicates();
+            open = false;
+        }
+
+        if !open {
+            self.show_duplicates = false;
+            self.duplicate_groups.clear();
+            self.duplicate_keep.clear();
+        }
+    }
+
+    fn merge_duplicates(&mut self) {
+        let Some(vault) = &mut self.vault else {
+            return;
+        };
+
+        let mut removed = 0;
+        for (group, keep) in self.duplicate_groups.iter().zip(&self.duplicate_keep) {
+            for id in group.iter().filter(|id| *id != keep) {
+                vault.delete_entry(*id);
+                removed += 1;
+                if self.selected_entry == Some(*id) {
+                    self.selected_entry = Some(*keep);
+                }
+            }
+        }
+
+        self.add_audit(AuditAction::DuplicatesMerged {
+            groups: self.duplicate_groups.len(),
+            removed,
+        });
+        self.update_search();
+        self.mark_dirty();
+        self.notify(format!("{} doublon(s) supprimé(s)", removed));
+    }
+
+    fn show_batch_create_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut should_close = false;
+
+        egui::Window::new("Créer plusieurs entrées")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label("Nombre d'entrées:");
+                ui.add(egui::Slider::new(&mut self.batch_count, 1..=50));
+
+                ui.label("Longueur:");
+                ui.add(egui::Slider::new(&mut self.generator_options.length, 4..=64));
+
+                ui.checkbox(&mut self.generator_options.include_uppercase, "Majuscules (A-Z)");
+                ui.checkbox(&mut self.generator_options.include_lowercase, "Minuscules (a-z)");
+                ui.checkbox(&mut self.generator_options.include_numbers, "Chiffres (0-9)");
+                ui.checkbox(&mut self.generator_options.include_symbols, "Symboles (!@#$...)");
+                symbol_preset_combo(ui, "batch_symbol_preset", &mut self.generator_options);
+                ui.checkbox(&mut self.generator_options.avoid_ambiguous, "Éviter caractères ambigus (il1Lo0O)");
+
+                ui.add_space(10.0);
+
+                if ui.button("🎲 Générer").clicked() {
+                    match generate_many(&self.generator_options, self.batch_count) {
+                        Ok(passwords) => {
+                            self.batch_drafts = passwords
+                                .into_iter()
+                                .enumerate()
+                                .map(|(i, pwd)| {
+                                    Entry::new(format!("Nouvelle entrée {}", i + 1), String::new(), pwd)
+                                })
+                                .collect();
+                        }
+                        Err(e) => {
+                            self.error_message = Some(e);
+                        }
+                    }
+                }
+
+                if !self.batch_drafts.is_empty() {
+                    ui.add_space(10.0);
+                    egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                        for draft in &mut self.batch_drafts {
+                            ui.horizontal(|ui| {
+                                ui.text_edit_singleline(&mut draft.name);
+                                ui.add(egui::TextEdit::singleline(&mut draft.login).hint_text("Identifiant"));
+                            });
+                        }
+                    });
+
+                    ui.add_space(10.0);
+
+                    if ui.button(format!("✓ Ajouter {} entrées", self.batch_drafts.len())).clicked() {
+                        let drafts = std::mem::take(&mut self.batch_drafts);
+                        let count = drafts.len();
+                        if let Some(vault) = &mut self.vault {
+                            for draft in drafts {
+                                let name = draft.name.clone();
+                                vault.add_entry(draft);
+                                self.audit_log.push(AuditEntry::new(AuditAction::EntryCreated(name)));
+                            }
+                        }
+                        self.update_search();
+                        self.mark_dirty();
+                        self.notify(format!("{} entrées créées", count));
+                        should_close = true;
+                    }
+                }
+            });
+
+        if !open || should_close {
+            self.show_batch_create = false;
+            self.batch_drafts.clear();
+            self.persist_generator_options();
+        }
+    }
+
+    fn show_generator_presets(&mut self, ui: &mut egui::Ui) {
+        let selected_text = self
+            .selected_preset
+            .and_then(|i| self.settings.generator_presets.get(i))
+            .map_or("Profil…", |(name, _)| name.as_str())
+            .to_string();
+
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_label("Profil")
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    for (i, (name, options)) in self.settings.generator_presets.iter().enumerate() {
+                        if ui.selectable_label(self.selected_preset == Some(i), name).clicked() {
+                            self.selected_preset = Some(i);
+                            self.generator_options = options.clone();
+                        }
+                    }
+                });
+
+            if let Some(i) = self.selected_preset {
+                if ui.button("🗑️").on_hover_text("Supprimer le profil").clicked() {
+                    if i < self.settings.generator_presets.len() {
+                        self.settings.generator_presets.remove(i);
+                    }
+                    self.selected_preset = None;
+                    self.save_settings();
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.preset_name).hint_text("Nom du profil"));
+            if ui.button("💾 Enregistrer").clicked() && !self.preset_name.trim().is_empty() {
+                let name = self.preset_name.trim().to_string();
+                let options = self.generator_options.clone();
+                match self.settings.generator_presets.iter().position(|(n, _)| *n == name) {
+                    Some(i) => {
+                        self.settings.generator_presets[i].1 = options;
+                        self.selected_preset = Some(i);
+                    }
+                    None => {
+                        self.settings.generator_presets.push((name, options));
+                        self.selected_preset = Some(self.settings.generator_presets.len() - 1);
+                    }
+                }
+                self.preset_name.clear();
+                self.save_settings();
+            }
+        });
+    }
+
+    fn save_settings(&mut self) {
+        if let Err(e) = self.settings.save() {
+            self.error_message = Some(format!("Erreur lors de l'enregistrement des préférences: {}", e));
+        }
+    }
+
+    fn show_csv_paste_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut should_close = false;
+
+        egui::Window::new("📥 Importer depuis texte")
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                ui.label("Collez un CSV avec l'en-tête name,login,password,url,notes,tags:");
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.csv_paste_text)
+                        .code_editor()
+                        .desired_rows(10)
+                        .desired_width(f32::INFINITY),
+                );
+
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button("📥 Importer").clicked() {
+                        match parse_csv(self.csv_paste_text.as_bytes()) {
+                            Ok(entries) => {
+                                let count = entries.len();
+                                if let Some(vault) = &mut self.vault {
+                                    for entry in entries {
+                                        vault.add_entry(entry);
+                                    }
+                                }
+                                self.add_audit(AuditAction::ImportCsv);
+                                self.update_search();
+                                self.mark_dirty();
+                                self.notify(format!("{} entrées importées", count));
+                                should_close = true;
+                            }
+                            Err(e) => {
+                                self.error_message = Some(format!("CSV invalide: {}", e));
+                            }
+                        }
+                    }
+
+                    if ui.button("❌ Annuler").clicked() {
+                        should_close = true;
+                    }
+                });
+            });
+
+        if !open || should_close {
+            self.show_csv_paste = false;
+            self.csv_paste_text.zeroize();
+        }
+    }
+
+    fn show_delete_confirmation(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+
+        egui::Window::new("⚠️ Confirmation")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label("Voulez-vous vraiment supprimer cette entrée ?");
+                ui.label("Cette action est irréversible.");
+
+                ui.add_space(20.0);
+
+                if ui.button("🗑️ Supprimer").clicked() {
+                    if let Some(id) = self.confirm_delete {
+                        if let Some(vault) = &mut self.vault {
+                            if let Some(entry) = vault.get_entry(id) {
+                                let name = entry.name.clone();
+                                vault.delete_entry(id);
+                                self.add_audit(AuditAction::EntryDeleted(name));
+                                self.selected_entry = None;
+                                self.update_search();
+                                self.mark_dirty();
+                                self.notify("Entrée supprimée".to_string());
+                            }
+                        }
+                    }
+                    self.confirm_delete = None;
+                }
+
+                if ui.button("❌ Annuler").clicked() {
+                    self.confirm_delete = None;
+                }
+            });
+
+        if !open {
+            self.confirm_delete = None;
+        }
+    }
+
+    fn show_overwrite_confirmation(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+
+        egui::Window::new("⚠️ Confirmation")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label("Un fichier existe déjà — écraser ?");
+                ui.label("Le coffre existant sera définitivement perdu.");
+
+                ui.add_space(20.0);
+
+                if ui.button("⚠️ Écraser").clicked() {
+                    self.confirm_overwrite = false;
+                    self.create_vault(true);
+                }
+
+                if ui.button("❌ Annuler").clicked() {
+                    self.confirm_overwrite = false;
+                }
+            });
+
+        if !open {
+            self.confirm_overwrite = false;
+        }
+    }
+
+    fn show_decoy_setup_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut should_close = false;
+
+        egui::Window::new("🎭 Coffre leurre")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label("Un second mot de passe ouvrira un coffre distinct et anodin.");
+                ui.label("Rien dans le fichier ne permet de savoir lequel est le vrai.");
+                ui.label("⚠️ Un coffre leurre existant sera remplacé par un coffre vide.");
+
+                ui.add_space(10.0);
+                ui.label("Mot de passe du leurre:");
+                ui.add(egui::TextEdit::singleline(&mut self.decoy_password).password(true));
+
+                ui.add_space(10.0);
+
+                if ui.button("💾 Créer le leurre").clicked() {
+                    should_close = self.create_decoy_vault();
+                }
+            });
+
+        if !open || should_close {
+            self.show_decoy_setup = false;
+            self.decoy_password.zeroize();
+        }
+    }
+
+    fn create_decoy_vault(&mut self) -> bool {
+        if self.decoy_password.len() < 8 {
+            self.error_message = Some("Le mot de passe doit contenir au moins 8 caractères".to_string());
+            return false;
+        }
+
+        let Some(path) = self.vault_path.clone() else {
+            return false;
+        };
+
+        // Depuis le leurre, écrire le second emplacement l'écraserait: même message qu'un échec
+        if self.vault_slot == VaultSlot::Secondary {
+            self.error_message = Some(DECOY_CREATE_FAILED.to_string());
+            return false;
+        }
+
+        // Le leurre ne doit pas réutiliser le mot de passe du coffre principal
+        if let Ok(true) = verify_master_password(&path, &self.decoy_password) {
+            self.error_message = Some("Ce mot de passe ouvre déjà un coffre".to_string());
+            return false;
+        }
+
+        match save_vault_slot(&Vault::new(), &path, &self.decoy_password, VaultSlot::Secondary) {
+            Ok(_) => {
+                // Le nombre d'entrées du principal, affiché à côté du leurre, le trahirait
+                if let Some(vault) = self.vault.as_mut().filter(|v| v.settings.expose_metadata) {
+                    vault.settings.expose_metadata = false;
+                    self.mark_dirty();
+                }
+                self.notify("Coffre leurre créé".to_string());
+                true
+            }
+            Err(_) => {
+                self.error_message = Some(DECOY_CREATE_FAILED.to_string());
+                false
+            }
+        }
+    }
+
+    fn show_key_shares_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+
+        egui::Window::new("🧩 Parts de clé")
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                ui.label("Découpe la clé du coffre en parts: un seuil de parts suffit à l'ouvrir,");
+                ui.label("aucune part seule ne révèle la clé. Rangez-les dans des lieux distincts.");
+                ui.weak("Les parts deviennent invalides après un ré-chiffrement du coffre.");
+
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    ui.label("Parts:");
+                    ui.add(egui::DragValue::new(&mut self.share_count).range(2..=16));
+                    ui.label("Seuil:");
+                    ui.add(egui::DragValue::new(&mut self.share_threshold).range(2..=self.share_count));
+                });
+
+                if ui.button("🧩 Générer les parts").clicked() {
+                    let session = self.session.lock().unwrap_or_else(|e| e.into_inner());
+                    let shares = session
+                        .as_ref()
+                        .map(|s| s.key_shares(self.share_count, self.share_threshold))
+                        .unwrap_or_default();
+                    drop(session);
+                    self.key_shares.zeroize();
+                    self.key_shares = shares;
+                }
+
+                let mut copied = None;
+                for (i, share) in self.key_shares.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{}.", i + 1));
+                        ui.add(egui::TextEdit::singleline(&mut share.as_str()).font(egui::TextStyle::Monospace));
+                        if ui
+                            .add_enabled(self.clipboard.is_available(), egui::Button::new("📋"))
+                            .on_disabled_hover_text(CLIPBOARD_UNAVAILABLE)
+                            .clicked()
+                        {
+                            copied = Some(share.clone());
+                        }
+                    });
+                }
+                if let Some(mut share) = copied {
+                    self.copy_to_clipboard(&share);
+                    share.zeroize();
+                }
+            });
+
+        if !open {
+            self.show_key_shares = false;
+            self.key_shares.zeroize();
+            self.key_shares.clear();
+        }
+    }
+
+    fn show_share_unlock_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut should_close = false;
+
+        egui::Window::new("🧩 Ouvrir avec des parts de clé")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label("Coffre: chemin saisi sur l'écran d'accueil.");
+                ui.label("Collez une part par ligne:");
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.share_input)
+                        .font(egui::TextStyle::Monospace)
+                        .desired_rows(5),
+                );
+
+                ui.add_space(10.0);
+
+                if ui.button("🔓 Ouvrir").clicked() {
+                    should_close = self.open_with_shares();
+                }
+            });
+
+        if !open || should_close {
+            self.show_share_unlock = false;
+            self.share_input.zeroize();
+        }
+    }
+
+    fn open_with_shares(&mut self) -> bool {
+        if self.new_vault_path.is_empty() {
+            self.error_message = Some("Veuillez spécifier un chemin de coffre".to_string());
+            return false;
+        }
+
+        let mut shares: Vec<String> = self
+            .share_input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect();
+
+        let path = PathBuf::from(&self.new_vault_path);
+        let result = combine_key(&shares)
+            .map_err(|e| e.to_string())
+            .and_then(|mut key| {
+                let loaded = open_vault_file_with_key(&path, &key).map_err(|e| e.to_string());
+                key.zeroize();
+                loaded
+            });
+        shares.zeroize();
+
+        match result {
+            Ok(loaded) => {
+                // Pas de mot de passe saisi: pas d'empreinte pour détecter sa réutilisation
+                self.finish_open(loaded, None, path);
+                true
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Erreur: {}", e));
+                false
+            }
+        }
+    }
+
+    fn show_reencrypt_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut should_close = false;
+
+        egui::Window::new("🔄 Ré-chiffrer le coffre")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label("Génère un nouveau salt et un nouveau nonce, puis re-dérive la clé.");
+                ui.label("Le mot de passe maître reste inchangé.");
+                ui.weak("À faire si le fichier du coffre a pu être copié.");
+                if let Some(ms) = self.last_derive_ms {
+                    ui.weak(format!("Dernier déverrouillage: dérivation en {} ms", ms));
+                }
+
+                ui.add_space(10.0);
+                ui.label("Mot de passe maître:");
+                ui.add(egui::TextEdit::singleline(&mut self.reencrypt_password).password(true));
+
+                ui.add_space(10.0);
+
+                if ui.button("🔄 Ré-chiffrer").clicked() {
+                    should_close = self.reencrypt_vault();
+                }
+            });
+
+        if !open || should_close {
+            self.show_reencrypt = false;
+            self.reencrypt_password.zeroize();
+        }
+    }
+
+    fn reencrypt_vault(&mut self) -> bool {
+        let (Some(vault), Some(path)) = (&self.vault, &self.vault_path) else {
+            return false;
+        };
+
+        match reencrypt_vault(vault, path, &self.reencrypt_password, self.vault_slot) {
+            Ok(session) => {
+                self.set_session(Some(session));
+                // Les modifications en attente viennent d'être écrites
+                self.dirty_since = None;
+                self.save_failed = false;
+                self.add_audit(AuditAction::VaultReencrypted);
+                self.notify("Coffre ré-chiffré".to_string());
+                true
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Erreur lors du ré-chiffrement: {}", e));
+                false
+            }
+        }
+    }
+
+    /// Gestion des dossiers: ordre d'affichage, renommage et fusion
+    fn show_folders_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut rename = None;
+        let mut move_folder = None;
+
+        let folders: Vec<(String, usize)> = self.vault.as_ref().map_or_else(Vec::new, |vault| {
+            vault
+                .folders()
+                .into_iter()
+                .map(|folder| {
+                    let count = vault
+                        .entries
+                        .iter()
+                        .filter(|e| e.folder.as_deref().map(str::to_lowercase) == Some(folder.to_lowercase()))
+                        .count();
+                    (folder, count)
+                })
+                .collect()
+        });
+
+        egui::Window::new("📁 Dossiers")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                if folders.is_empty() {
+                    ui.weak("Aucun dossier. Choisissez-en un dans l'éditeur d'une entrée.");
+                }
+
+                for (index, (folder, count)) in folders.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(index > 0, egui::Button::new("⬆").small()).on_hover_text("Monter").clicked() {
+                            move_folder = Some((folder.clone(), true));
+                        }
+                        if ui
+                            .add_enabled(index + 1 < folders.len(), egui::Button::new("⬇").small())
+                            .on_hover_text("Descendre")
+                            .clicked()
+                        {
+                            move_folder = Some((folder.clone(), false));
+                        }
+                        ui.label(format!("📁 {} ({})", folder, count));
+                        if ui.small_button("✏️").on_hover_text("Renommer").clicked() {
+                            self.folder_rename = Some((folder.clone(), folder.clone()));
+                        }
+                    });
+                }
+
+                if let Some((old, new)) = &mut self.folder_rename {
+                    ui.separator();
+                    ui.label(format!("Nouveau nom pour « {} »:", old));
+                    ui.text_edit_singleline(new);
+                    let merged_into = folders.iter().find(|(f, _)| {
+                        f.to_lowercase() == new.trim().to_lowercase() && f.to_lowercase() != old.to_lowercase()
+                    });
+                    if let Some((existing, _)) = merged_into {
+                        ui.weak(format!("Les entrées rejoindront le dossier existant « {} »", existing));
+                    }
+                    ui.horizontal(|ui| {
+                        if ui.button("✅ Renommer").clicked() {
+                            rename = Some((old.clone(), new.clone()));
+                        }
+                        if ui.button("❌ Annuler").clicked() {
+                            rename = Some((String::new(), String::new()));
+                        }
+                    });
+                }
+
+                ui.add_space(10.0);
+                ui.weak("Un dossier disparaît dès qu'il ne contient plus d'entrée.");
+            });
+
+        if let Some((folder, up)) = move_folder {
+            if self.vault.as_mut().is_some_and(|v| v.move_folder(&folder, up)) {
+                self.mark_dirty();
+            }
+        }
+
+        if let Some((old, new)) = rename {
+            self.folder_rename = None;
+            if !old.is_empty() {
+                match self.vault.as_mut().map(|v| v.rename_folder(&old, &new)) {
+                    Some(Ok((moved, target))) => {
+                        self.mark_dirty();
+                        self.notify(format!("{} entrée(s) déplacée(s) vers « {} »", moved, target));
+                    }
+                    Some(Err(e)) => self.error_message = Some(e),
+                    None => {}
+                }
+            }
+        }
+
+        if !open {
+            self.show_folders = false;
+            self.folder_rename = None;
+        }
+    }
+
+    /// Ouvre les sites de plusieurs entrées (rien n'est copié), après confirmation
+    fn show_batch_open_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut urls_to_open = Vec::new();
+
+        let candidates: Vec<(Uuid, String)> = self.vault.as_ref().map_or_else(Vec::new, |vault| {
+            let mut candidates: Vec<(Uuid, String)> = vault
+                .entries
+                .iter()
+                .filter(|e| e.web_url().is_some())
+                .map(|e| (e.id, e.name.clone()))
+                .collect();
+            candidates.sort_by_key(|(_, name)| name.to_lowercase());
+            candidates
+        });
+
+        egui::Window::new("🌐 Ouvrir des sites")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label(format!("Sites à ouvrir ({} au plus):", BATCH_OPEN_MAX));
+
+                egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for (id, name) in &candidates {
+                        let mut checked = self.batch_open_selection.contains(id);
+                        let full = self.batch_open_selection.len() >= BATCH_OPEN_MAX;
+                        if ui.add_enabled(checked || !full, egui::Checkbox::new(&mut checked, name)).changed() {
+                            if checked {
+                                self.batch_open_selection.push(*id);
+                            } else {
+                                self.batch_open_selection.retain(|s| s != id);
+                            }
+                            self.confirm_batch_open = false;
+                        }
+                    }
+                });
+
+                if candidates.is_empty() {
+                    ui.weak("Aucune entrée n'a d'URL valide.");
+                }
+
+                ui.add_space(10.0);
+                let count = self.batch_open_selection.len();
+
+                if self.confirm_batch_open {
+                    ui.label(format!("Ouvrir {} onglet(s) dans le navigateur?", count));
+                    ui.horizontal(|ui| {
+                        if ui.button("✅ Ouvrir").clicked() {
+                            if let Some(vault) = &self.vault {
+                                urls_to_open = vault.web_urls(&self.batch_open_selection, BATCH_OPEN_MAX);
+                            }
+                            self.confirm_batch_open = false;
+                        }
+                        if ui.button("❌ Annuler").clicked() {
+                            self.confirm_batch_open = false;
+                        }
+                    });
+                } else if ui
+                    .add_enabled(count > 0, egui::Button::new(format!("🌐 Ouvrir ({})", count)))
+                    .clicked()
+                {
+                    self.confirm_batch_open = true;
+                }
+            });
+
+        for url in urls_to_open {
+            ctx.open_url(egui::OpenUrl::new_tab(url));
+        }
+
+        if !open {
+            self.show_batch_open = false;
+            self.confirm_batch_open = false;
+        }
+    }
+
+    fn show_save_as_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut should_close = false;
+
+        egui::Window::new("💾 Enregistrer sous…")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label("Chemin du coffre:");
+                ui.text_edit_singleline(&mut self.save_as_path);
+
+                ui.add_space(10.0);
+                ui.label("Mot de passe maître:");
+                ui.add(egui::TextEdit::singleline(&mut self.save_as_password).password(true));
+
+                ui.add_space(10.0);
+
+                if ui.button("💾 Enregistrer").clicked() {
+                    should_close = self.save_temporary_vault();
+                }
+            });
+
+        if !open || should_close {
+            self.show_save_as = false;
+            self.save_as_password.zeroize();
+        }
+    }
+
+    /// Premier enregistrement d'un coffre temporaire: il devient un coffre ordinaire
+    fn save_temporary_vault(&mut self) -> bool {
+        if self.save_as_password.len() < 8 {
+            self.error_message = Some("Le mot de passe maître doit contenir au moins 8 caractères".to_string());
+            return false;
+        }
+
+        if self.save_as_path.is_empty() {
+            self.error_message = Some("Veuillez spécifier un chemin pour le coffre".to_string());
+            return false;
+        }
+
+        let path = PathBuf::from(&self.save_as_path);
+        if path.exists() {
+            self.error_message = Some("Un fichier existe déjà à cet emplacement".to_string());
+            return false;
+        }
+
+        let Some(vault) = &self.vault else {
+            return false;
+        };
+
+        match create_vault_file_with_params(vault, &path, &self.save_as_password, self.kdf_params.clone()) {
+            Ok(session) => {
+                self.set_session(Some(session));
+                self.master_fingerprint = MasterFingerprint::new(&self.save_as_password).ok();
+                self.vault_path = Some(path);
+                self.dirty_since = None;
+                self.save_failed = false;
+                self.save_as_path.clear();
+                self.add_audit(AuditAction::VaultCreated);
+                self.notify("Coffre enregistré".to_string());
+                self.check_master_reuse();
+                true
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Erreur lors de l'enregistrement: {}", e));
+                false
+            }
+        }
+    }
+
+    fn show_backup_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+
+        egui::Window::new("💾 Sauvegarde chiffrée")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                self.show_breach_export_warning(ui);
+                ui.label("Fichier de sauvegarde (.mdpx):");
+                ui.text_edit_singleline(&mut self.backup_path);
+
+                ui.add_space(10.0);
+                ui.label("Mot de passe de la sauvegarde:");
+                ui.add(egui::TextEdit::singleline(&mut self.backup_password).password(true));
+                ui.weak("Indépendant du mot de passe maître du coffre ouvert.");
+                ui.weak("Gardé jusqu'à la fermeture de cette fenêtre, puis effacé.");
+
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button("📤 Exporter").clicked() {
+                        self.export_backup();
+                    }
+                    if ui.button("📥 Importer et fusionner").clicked() {
+                        self.import_backup();
+                    }
+                    if ui
+                        .button("✔️ Vérifier la sauvegarde")
+                        .on_hover_text("Déchiffre le fichier sans l'importer")
+                        .clicked()
+                    {
+                        self.check_backup_opens();
+                    }
+                });
+                if ui
+                    .button("♻️ Remplacer le coffre")
+                    .on_hover_text("Restauration complète: les entrées actuelles sont remplacées")
+                    .clicked()
+                {
+                    self.replace_confirm_text.clear();
+                    self.confirm_replace_import = true;
+                }
+
+                ui.add_space(10.0);
+                ui.weak("Manifeste (empreinte SHA-256) enregistré à côté de la sauvegarde:");
+                ui.horizontal(|ui| {
+                    if ui.button("🧾 Écrire le manifeste").clicked() {
+                        self.write_backup_manifest();
+                    }
+                    if ui.button("🔍 Vérifier").clicked() {
+                        self.verify_backup_manifest();
+                    }
+                });
+            });
+
+        if !open {
+            self.show_backup = false;
+            self.backup_password.zeroize();
+        }
+    }
+
+    /// Avertit (sans bloquer) que l'export contiendra des mots de passe présents dans des fuites
+    fn show_breach_export_warning(&self, ui: &mut egui::Ui) {
+        let Some(vault) = &self.vault else {
+            return;
+        };
+        let breached = vault.breached_names();
+        if breached.is_empty() {
+            return;
+        }
+
+        ui.group(|ui| {
+            ui.colored_label(
+                egui::Color32::from_rgb(220, 53, 69),
+                format!("🚨 {} mot(s) de passe compromis seront exportés:", breached.len()),
+            );
+            egui::ScrollArea::vertical().max_height(100.0).show(ui, |ui| {
+                for name in &breached {
+                    ui.label(format!("• {}", name));
+                }
+            });
+            ui.weak("Pensez à les changer avant de vous fier à cet export.");
+        });
+        ui.add_space(10.0);
+    }
+
+    fn show_csv_export_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+
+        egui::Window::new("📄 Export CSV")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                self.show_breach_export_warning(ui);
+                ui.label("Fichier de destination (.csv):");
+                ui.text_edit_singleline(&mut self.csv_export_path);
+
+                ui.add_space(10.0);
+                ui.checkbox(&mut self.csv_export_encrypt, "🔒 Chiffrer avec un mot de passe à usage unique (.csv.enc)");
+
+                ui.add_space(10.0);
+                if self.csv_export_encrypt {
+                    // Colonnes fixes: l'export chiffré doit pouvoir être relu tel quel
+                    ui.weak("Colonnes: nom, identifiant, mot de passe, URL, notes, tags.");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.csv_export_password)
+                            .password(true)
+                            .hint_text("Mot de passe de l'export"),
+                    );
+                } else {
+                    let options = &mut self.csv_export_options;
+                    ui.label("Colonnes:");
+                    ui.checkbox(&mut options.include_id, "Identifiant interne (id)");
+                    ui.checkbox(&mut options.include_name, "Nom");
+                    ui.checkbox(&mut options.include_login, "Identifiant");
+                    ui.checkbox(&mut options.include_password, "Mot de passe");
+                    ui.checkbox(&mut options.include_url, "URL");
+                    ui.checkbox(&mut options.include_notes, "Notes");
+                    ui.checkbox(&mut options.include_tags, "Tags");
+                    ui.checkbox(&mut options.include_timestamps, "Dates de création et de modification");
+
+                    ui.add_space(10.0);
+                    ui.add_enabled(
+                        options.include_password,
+                        egui::Checkbox::new(&mut options.plaintext, "⚠️ Mots de passe en clair"),
+                    );
+                }
+
+                ui.add_space(10.0);
+
+                if ui.button("📤 Exporter").clicked() {
+                    if !self.csv_export_encrypt
+                        && self.csv_export_options.include_password
+                        && self.csv_export_options.plaintext
+                    {
+                        self.confirm_export_plain = Some(PlainExport::Csv);
+                    } else {
+                        self.export_csv_file();
+                    }
+                }
+
+                ui.add_space(10.0);
+                ui.collapsing("🧾 JSON (tous les champs)", |ui| {
+                    ui.label("Conserve tags, URL, TOTP et liens, que le CSV aplatit ou ignore.");
+                    ui.text_edit_singleline(&mut self.json_path);
+                    ui.checkbox(&mut self.json_include_secrets, "⚠️ Inclure mots de passe et secrets TOTP en clair");
+                    ui.horizontal(|ui| {
+                        if ui.button("📤 Exporter en JSON").clicked() {
+                            if self.json_include_secrets {
+                                self.confirm_export_plain = Some(PlainExport::Json);
+                            } else {
+                                self.export_json_file();
+                            }
+                        }
+                        if ui.button("📥 Importer un JSON").clicked() {
+                            self.import_json_file();
+                        }
+                    });
+                });
+            });
+
+        if !open {
+            self.show_csv_export = false;
+            self.csv_export_password.zeroize();
+        }
+    }
+
+    fn show_export_plain_confirmation(&mut self, ctx: &egui::Context, kind: PlainExport) {
+        let mut open = true;
+
+        egui::Window::new("⚠️ Confirmation")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label("Les mots de passe seront écrits en clair dans le fichier.");
+                if kind == PlainExport::Json {
+                    ui.label("Les secrets TOTP aussi.");
+                }
+                ui.label("Toute personne ayant accès au fichier pourra les lire.");
+
+                ui.add_space(20.0);
+
+                if ui.button("⚠️ Exporter en clair").clicked() {
+                    self.confirm_export_plain = None;
+                    match kind {
+                        PlainExport::Csv => self.export_csv_file(),
+                        PlainExport::Json => self.export_json_file(),
+                    }
+                }
+
+                if ui.button("❌ Annuler").clicked() {
+                    self.confirm_export_plain = None;
+                }
+            });
+
+        if !open {
+            self.confirm_export_plain = None;
+        }
+    }
+
+    fn export_csv_file(&mut self) {
+        if self.csv_export_path.is_empty() {
+            self.error_message = Some("Veuillez spécifier un fichier de destination".to_string());
+            return;
+        }
+
+        if self.csv_export_encrypt {
+            self.export_csv_encrypted_file();
+            return;
+        }
+
+        let Some(vault) = &self.vault else {
+            return;
+        };
+
+        match export_csv(vault, &PathBuf::from(&self.csv_export_path), &self.csv_export_options) {
+            Ok(_) => {
+                if self.csv_export_options.include_password && self.csv_export_options.plaintext {
+                    self.add_audit(AuditAction::ExportPlaintext);
+                }
+                self.notify("Export CSV terminé".to_string());
+            }
+            Err(e) => self.error_message = Some(format!("Erreur lors de l'export: {}", e)),
+        }
+    }
+
+    fn export_json_file(&mut self) {
+        if self.json_path.trim().is_empty() {
+            self.error_message = Some("Veuillez spécifier un fichier de destination".to_string());
+            return;
+        }
+
+        let Some(vault) = &self.vault else {
+            return;
+        };
+
+        let include_secrets = self.json_include_secrets;
+        match export_json(vault, &PathBuf::from(self.json_path.trim()), include_secrets) {
+            Ok(_) => {
+                self.add_audit(AuditAction::ExportJson { include_secrets });
+                self.notify("Export JSON terminé".to_string());
+            }
+            Err(e) => self.error_message = Some(format!("Erreur lors de l'export: {}", e)),
+        }
+    }
+
+    fn import_json_file(&mut self) {
+        if self.json_path.trim().is_empty() {
+            self.error_message = Some("Veuillez spécifier un fichier à importer".to_string());
+            return;
+        }
+
+        match import_json(&PathBuf::from(self.json_path.trim())) {
+            Ok(entries) => {
+                let changed = self.vault.as_mut().map_or(0, |v| v.merge_entries(entries));
+                self.add_audit(AuditAction::ImportJson);
+                self.update_search();
+                self.mark_dirty();
+                self.notify(format!("{} entrées importées ou mises à jour", changed));
+            }
+            Err(e) => self.error_message = Some(format!("JSON invalide: {}", e)),
+        }
+    }
+
+    fn export_csv_encrypted_file(&mut self) {
+        if self.csv_export_password.len() < 8 {
+            self.error_message = Some("Le mot de passe de l'export doit contenir au moins 8 caractères".to_string());
+            return;
+        }
+
+        let Some(vault) = &self.vault else {
+            return;
+        };
+
+        match export_csv_encrypted(vault, &PathBuf::from(&self.csv_export_path), &self.csv_export_password) {
+            Ok(_) => {
+                self.add_audit(AuditAction::ExportCsvEncrypted);
+                self.csv_export_password.zeroize();
+                self.notify("Export CSV chiffré terminé".to_string());
+            }
+            Err(e) => self.error_message = Some(format!("Erreur lors de l'export: {}", e)),
+        }
+    }
+
+    fn export_backup(&mut self) {
+        if self.backup_path.is_empty() || self.backup_password.len() < 8 {
+            self.error_message =
+                Some("Chemin et mot de passe (8 caractères min.) requis".to_string());
+            return;
+        }
+
+        let Some(vault) = &self.vault else {
+            return;
+        };
+
+        let path = PathBuf::from(&self.backup_path);
+        let expected = vault.entries.len();
+        match export_encrypted(vault, &path, &self.backup_password) {
+            Ok(_) => {
+                self.add_audit(AuditAction::ExportEncrypted);
+                // Relire tout de suite: une sauvegarde illisible se découvre maintenant, pas à la restauration
+                match verify_backup(&path, &self.backup_password, expected) {
+                    Ok(count) => self.notify(format!("Sauvegarde exportée et vérifiée ({} entrées)", count)),
+                    Err(e) => {
+                        self.error_message = Some(format!("⚠️ Sauvegarde écrite mais non vérifiée: {}", e));
+                    }
+                }
+                // Conservé jusqu'à la fermeture de la fenêtre, pour « Vérifier la sauvegarde »
+            }
+            Err(e) => self.error_message = Some(format!("Erreur lors de l'export: {}", e)),
+        }
+    }
+
+    /// Ouvre la sauvegarde indiquée sans l'importer, pour s'assurer qu'elle se restaurera
+    fn check_backup_opens(&mut self) {
+        if self.backup_path.is_empty() {
+            self.error_message = Some("Veuillez spécifier un fichier de sauvegarde".to_string());
+            return;
+        }
+
+        let expected = self.vault.as_ref().map_or(0, |v| v.entries.len());
+        match verify_backup(&PathBuf::from(&self.backup_path), &self.backup_password, expected) {
+            Ok(count) => self.notify(format!("Sauvegarde lisible, {} entrées comme le coffre", count)),
+            Err(e) => self.error_message = Some(format!("⚠️ Sauvegarde non vérifiée: {}", e)),
+        }
+    }
+
+    fn show_replace_import_confirmation(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let count = self.vault.as_ref().map_or(0, |v| v.entries.len());
+        let temporary = self.is_temporary();
+
+        egui::Window::new("⚠️ Remplacer le coffre")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "Les {} entrées actuelles seront remplacées par celles de la sauvegarde.",
+                    count
+                ));
+                if temporary {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(255, 193, 7),
+                        "Coffre temporaire: aucune copie de sécurité ne sera faite.",
+                    );
+                } else {
+                    ui.label("Une copie du fichier actuel est faite automatiquement avant le remplacement.");
+                }
+                ui.add_space(10.0);
+                ui.label(format!("Tapez {} pour confirmer:", REPLACE_CONFIRM_WORD));
+                ui.text_edit_singleline(&mut self.replace_confirm_text);
+
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    let confirmed = self.replace_confirm_text.trim() == REPLACE_CONFIRM_WORD;
+                    if ui.add_enabled(confirmed, egui::Button::new("♻️ Remplacer")).clicked() {
+                        self.confirm_replace_import = false;
+                        self.replace_with_backup();
+                    }
+                    if ui.button("❌ Annuler").clicked() {
+                        self.confirm_replace_import = false;
+                    }
+                });
+            });
+
+        if !open {
+            self.confirm_replace_import = false;
+        }
+    }
+
+    /// Restauration complète: copie de sécurité du fichier actuel, puis remplacement des entrées
+    fn replace_with_backup(&mut self) {
+        if self.backup_path.is_empty() {
+            self.error_message = Some("Veuillez spécifier un fichier de sauvegarde".to_string());
+            return;
+        }
+
+        let entries = match import_encrypted(&PathBuf::from(&self.backup_path), &self.backup_password) {
+            Ok(entries) => entries,
+            Err(e) => {
+                self.error_message = Some(format!("Impossible d'ouvrir la sauvegarde: {}", e));
+                return;
+            }
+        };
+
+        // La copie de sécurité doit contenir les modifications encore en attente
+        if !self.save_now() {
+            if let Some(message) = self.error_message.take() {
+                self.error_message = Some(format!("{}. Coffre inchangé.", message));
+            }
+            return;
+        }
+
+        let safety_copy = match self.vault_path.as_deref().map(backup_vault_file) {
+            Some(Ok(path)) => Some(path),
+            Some(Err(e)) => {
+                self.error_message = Some(format!("Copie de sécurité impossible, coffre inchangé: {}", e));
+                return;
+            }
+            None => None,
+        };
+
+        let Some(vault) = &mut self.vault else {
+            return;
+        };
+        let count = vault.replace_entries(entries);
+        self.selected_entry = None;
+        self.add_audit(AuditAction::VaultReplaced { count });
+        self.update_search();
+        self.mark_dirty();
+        self.backup_password.zeroize();
+        self.notify(match safety_copy {
+            Some(path) => format!("Coffre remplacé ({} entrées), copie: {}", count, path.display()),
+            None => format!("Coffre remplacé ({} entrées), sans copie: coffre temporaire", count),
+        });
+    }
+
+    fn backup_manifest_path(&self) -> PathBuf {
+        PathBuf::from(format!("{}.manifest.json", self.backup_path))
+    }
+
+    fn write_backup_manifest(&mut self) {
+        if self.backup_path.is_empty() {
+            self.error_message = Some("Veuillez spécifier un fichier de sauvegarde".to_string());
+            return;
+        }
+
+        let Some(vault) = &self.vault else {
+            return;
+        };
+
+        let manifest_path = self.backup_manifest_path();
+        match write_manifest(vault, &PathBuf::from(&self.backup_path), &manifest_path) {
+            Ok(_) => self.notify(format!("Manifeste écrit: {}", manifest_path.display())),
+            Err(e) => self.error_message = Some(format!("Erreur lors de l'écriture du manifeste: {}", e)),
+        }
+    }
+
+    fn verify_backup_manifest(&mut self) {
+        if self.backup_path.is_empty() {
+            self.error_message = Some("Veuillez spécifier un fichier de sauvegarde".to_string());
+            return;
+        }
+
+        match verify_manifest(&PathBuf::from(&self.backup_path), &self.backup_manifest_path()) {
+            Ok(true) => self.notify("Sauvegarde intacte".to_string()),
+            Ok(false) => {
+                self.error_message = Some("La sauvegarde ne correspond pas à son manifeste".to_string())
+            }
+            Err(e) => self.error_message = Some(format!("Erreur lors de la vérification: {}", e)),
         }
+    }
 
-        if !entry.tags.is_empty() {
-            ui.add_space(10.0);
-            ui.group(|ui| {
-                ui.label("Tags:");
-                ui.horizontal_wrapped(|ui| {
-                    for tag in &entry.tags {
-                        ui.label(format!("🏷️ {}", tag));
-                    }
-                });
-            });
+    fn import_backup(&mut self) {
+        if self.backup_path.is_empty() {
+            self.error_message = Some("Veuillez spécifier un fichier de sauvegarde".to_string());
+            return;
         }
 
-        ui.add_space(10.0);
-        ui.label(format!("Créé: {}", entry.created_at.format("%Y-%m-%d %H:%M")));
-        ui.label(format!("Modifié: {}", entry.modified_at.format("%Y-%m-%d %H:%M")));
+        match import_encrypted(&PathBuf::from(&self.backup_path), &self.backup_password) {
+            Ok(entries) => {
+                let changed = self.vault.as_mut().map_or(0, |v| v.merge_entries(entries));
+                self.add_audit(AuditAction::ImportEncrypted);
+                self.update_search();
+                self.mark_dirty();
+                self.notify(format!("{} entrées importées ou mises à jour", changed));
+                self.backup_password.zeroize();
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Impossible d'ouvrir la sauvegarde: {}", e));
+            }
+        }
     }
 
-    fn show_entry_editor(&mut self, ctx: &egui::Context) {
+    fn show_settings_window(&mut self, ctx: &egui::Context) {
         let mut open = true;
-        let mut should_close = false;
-        let mut should_save = false;
-        let mut error_msg = None;
+        let mut changed = false;
+        let mut vault_changed = false;
 
-        egui::Window::new("Éditer l'entrée")
+        egui::Window::new("⚙️ Préférences")
             .open(&mut open)
             .collapsible(false)
             .show(ctx, |ui| {
-                if let Some(entry) = &mut self.editing_entry {
-                    ui.label("Nom du service:");
-                    ui.text_edit_singleline(&mut entry.name);
-
-                    ui.add_space(10.0);
-                    ui.label("Identifiant / Login:");
-                    ui.text_edit_singleline(&mut entry.login);
-
-                    ui.add_space(10.0);
-                    ui.label("Mot de passe:");
-                    ui.horizontal(|ui| {
-                        ui.text_edit_singleline(&mut entry.password);
-                        if ui.button("🎲 Générer").clicked() {
-                            self.show_generator = true;
-                        }
-                    });
+                ui.label("Affichage des dates:");
+                ui.horizontal(|ui| {
+                    for choice in [TimeDisplay::Local, TimeDisplay::Utc] {
+                        changed |= ui
+                            .radio_value(&mut self.settings.time_display, choice, choice.label())
+                            .changed();
+                    }
+                });
 
-                    ui.add_space(10.0);
-                    ui.label("URL (optionnel):");
-                    let mut url = entry.url.clone().unwrap_or_default();
-                    ui.text_edit_singleline(&mut url);
-                    entry.url = if url.is_empty() { None } else { Some(url) };
+                ui.label("Format (strftime):");
+                changed |= ui.text_edit_singleline(&mut self.settings.date_format).changed();
+                ui.label(format!("Aperçu: {}", self.settings.format_timestamp(&chrono::Utc::now())));
 
-                    ui.add_space(10.0);
-                    ui.label("Notes (optionnel):");
-                    let mut notes = entry.notes.clone().unwrap_or_default();
-                    ui.text_edit_multiline(&mut notes);
-                    entry.notes = if notes.is_empty() { None } else { Some(notes) };
+                ui.separator();
+                ui.label("Ancienneté des mots de passe (jours):");
+                ui.horizontal(|ui| {
+                    ui.label("Orange après");
+                    changed |= ui
+                        .add(egui::DragValue::new(&mut self.settings.age_warning_days).range(1..=3650))
+                        .changed();
+                    ui.label("rouge après");
+                    changed |= ui
+                        .add(egui::DragValue::new(&mut self.settings.age_critical_days).range(1..=3650))
+                        .changed();
+                });
 
-                    ui.add_space(20.0);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label("« Tout afficher » masqué après");
+                    changed |= ui
+                        .add(egui::DragValue::new(&mut self.settings.reveal_all_seconds).range(5..=300).suffix(" s"))
+                        .changed();
+                });
+                changed |= ui
+                    .checkbox(
+                        &mut self.settings.notify_clipboard_cleared,
+                        "Notifier l'effacement du presse-papiers",
+                    )
+                    .changed();
+                #[cfg(feature = "global-hotkey")]
+                ui.horizontal(|ui| {
+                    ui.label("Verrouillage global:");
+                    ui.add(egui::TextEdit::singleline(&mut self.settings.lock_hotkey).desired_width(120.0))
+                        .on_hover_text("Actif même quand la fenêtre n'a pas le focus, ex. Ctrl+Alt+L");
+                    if ui.button("Appliquer").clicked() {
+                        self.register_lock_hotkey();
+                        changed = true;
+                    }
+                });
+                #[cfg(feature = "deep-link")]
+                {
+                    changed |= ui
+                        .checkbox(&mut self.settings.deep_links_enabled, "Ouvrir les liens mdp://entry/…")
+                        .on_hover_text("Un script ou une extension peut demander l'affichage d'une entrée")
+                        .changed();
+                }
 
+                ui.separator();
+                ui.label("Modèles « Copier au format » ({name}, {login}, {password}, {url}, {host}, {notes}):");
+                let mut removed_template = None;
+                for (i, template) in self.settings.copy_templates.iter_mut().enumerate() {
                     ui.horizontal(|ui| {
-                        if ui.button("💾 Sauvegarder").clicked() {
-                            if entry.name.is_empty() || entry.login.is_empty() || entry.password.is_empty() {
-                                error_msg = Some("Nom, login et mot de passe requis".to_string());
-                            } else {
-                                should_save = true;
-                                should_close = true;
-                            }
+                        changed |= ui
+                            .add(egui::TextEdit::singleline(&mut template.name).desired_width(100.0))
+                            .changed();
+                        changed |= ui.text_edit_singleline(&mut template.template).changed();
+                        egui::ComboBox::from_id_source(("copy_template_escape", i))
+                            .selected_text(template.escape.label())
+                            .show_ui(ui, |ui| {
+                                for escape in TemplateEscape::ALL {
+                                    changed |= ui.selectable_value(&mut template.escape, escape, escape.label()).changed();
+                                }
+                            });
+                        if ui.small_button("❌").clicked() {
+                            removed_template = Some(i);
                         }
+                    });
+                }
+                if let Some(i) = removed_template {
+                    self.settings.copy_templates.remove(i);
+                    changed = true;
+                }
+                if ui.button("➕ Ajouter un modèle").clicked() {
+                    self.settings.copy_templates.push(CopyTemplate {
+                        name: "Nouveau modèle".to_string(),
+                        template: "{login}:{password}".to_string(),
+                        escape: TemplateEscape::None,
+                    });
+                    changed = true;
+                }
 
-                        if ui.button("❌ Annuler").clicked() {
-                            should_close = true;
+                if let Some(vault) = &mut self.vault {
+                    ui.separator();
+                    ui.label("Ce coffre:");
+                    let placeholder = vault.settings.display_name(self.vault_path.as_deref());
+                    let mut name = vault.settings.name.clone().unwrap_or_default();
+                    ui.horizontal(|ui| {
+                        if ui.add(egui::TextEdit::singleline(&mut name).hint_text(placeholder)).changed() {
+                            vault.settings.name = Some(name).filter(|n| !n.trim().is_empty());
+                            vault_changed = true;
                         }
+                        vault_changed |= ui.color_edit_button_srgb(&mut vault.settings.color).changed();
+                    });
+                    vault_changed |= ui
+                        .checkbox(
+                            &mut vault.settings.expose_metadata,
+                            "Aperçu sans déverrouillage (nombre d'entrées et date lisibles en clair)",
+                        )
+                        .on_hover_text("Désactivé à la création d'un coffre leurre, que cet aperçu trahirait")
+                        .changed();
+                    ui.horizontal(|ui| {
+                        ui.label("Verrouillage auto après");
+                        vault_changed |= ui
+                            .add(egui::DragValue::new(&mut vault.settings.auto_lock_seconds).range(0..=86400))
+                            .changed();
+                        ui.label("s (0 = jamais)");
                     });
                 }
-            });
-
-        if !open {
-            should_close = true;
-        }
-
-        if let Some(msg) = error_msg {
-            self.error_message = Some(msg);
-        }
 
-        if should_save {
-            if let Some(entry) = &self.editing_entry {
-                let entry_clone = entry.clone();
-                let entry_id = entry.id;
-                let entry_name = entry.name.clone();
-                
-                if let Some(vault) = &mut self.vault {
-                    let is_existing = vault.get_entry(entry_id).is_some();
-                    
-                    let action = if is_existing {
-                        AuditAction::EntryUpdated(entry_name.clone())
-                    } else {
-                        AuditAction::EntryCreated(entry_name.clone())
-                    };
+                ui.separator();
+                ui.label("Sécurité:");
+                ui.horizontal(|ui| {
+                    if ui.button("🔄 Ré-chiffrer le coffre").clicked() {
+                        self.show_reencrypt = true;
+                    }
+                    if ui.button("🧩 Parts de clé").clicked() {
+                        self.show_key_shares = true;
+                    }
+                });
 
-                    vault.update_entry(entry_id, entry_clone.clone());
-                    if !vault.entries.iter().any(|e| e.id == entry_clone.id) {
-                        vault.add_entry(entry_clone);
+                ui.separator();
+                ui.label("Configuration (sans le coffre):");
+                ui.add(egui::TextEdit::singleline(&mut self.config_path).hint_text("Chemin du fichier"));
+                ui.horizontal(|ui| {
+                    let path = PathBuf::from(&self.config_path);
+                    if ui.button("📤 Exporter").clicked() && !self.config_path.is_empty() {
+                        match self.settings.export_config(&path) {
+                            Ok(_) => self.notify("Configuration exportée".to_string()),
+                            Err(e) => self.error_message = Some(format!("Erreur: {}", e)),
+                        }
+                    }
+                    if ui.button("📥 Importer").clicked() && !self.config_path.is_empty() {
+                        match self.settings.import_config(&path, self.config_import_security) {
+                            Ok(_) => {
+                                changed = true;
+                                self.notify("Configuration importée".to_string());
+                            }
+                            Err(e) => self.error_message = Some(format!("Erreur: {}", e)),
+                        }
                     }
+                });
+                ui.checkbox(
+                    &mut self.config_import_security,
+                    "Importer aussi le raccourci de verrouillage et les liens mdp://",
+                )
+                .on_hover_text("Réglages de sécurité: laissés tels quels par défaut");
+            });
 
-                    self.add_audit(action);
-                    self.update_search();
-                    self.success_message = Some("Entrée sauvegardée".to_string());
-                    self.update_activity();
-                }
-            }
+        if changed {
+            self.save_settings();
         }
 
-        if should_close {
-            self.show_entry_modal = false;
-            self.editing_entry = None;
+        if vault_changed {
+            self.mark_dirty();
+        }
+
+        if !open {
+            self.show_settings = false;
         }
     }
 
-    fn show_password_generator(&mut self, ctx: &egui::Context) {
+    fn show_health_window(&mut self, ctx: &egui::Context) {
         let mut open = true;
+        let mut select = None;
+        let mut dismiss_notes = None;
+        let mut check_breaches = false;
+        if self.strength_histogram.is_none() {
+            self.strength_histogram = self.vault.as_ref().map(Vault::strength_histogram);
+        }
+        let histogram = self.strength_histogram;
 
-        egui::Window::new("Générateur de mot de passe")
+        egui::Window::new("🩺 Santé du coffre")
             .open(&mut open)
             .collapsible(false)
+            .default_width(400.0)
             .show(ctx, |ui| {
-                ui.label("Longueur:");
-                ui.add(egui::Slider::new(&mut self.generator_options.length, 4..=64));
+                let Some(vault) = &self.vault else {
+                    return;
+                };
 
-                ui.checkbox(&mut self.generator_options.include_uppercase, "Majuscules (A-Z)");
-                ui.checkbox(&mut self.generator_options.include_lowercase, "Minuscules (a-z)");
-                ui.checkbox(&mut self.generator_options.include_numbers, "Chiffres (0-9)");
-                ui.checkbox(&mut self.generator_options.include_symbols, "Symboles (!@#$...)");
-                ui.checkbox(&mut self.generator_options.avoid_ambiguous, "Éviter caractères ambigus (il1Lo0O)");
+                if let Some(counts) = histogram {
+                    show_strength_histogram(ui, &counts);
+                    ui.add_space(10.0);
+                }
 
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.breach_list_path)
+                            .hint_text("Liste de fuites (SHA1:COMPTE)"),
+                    );
+                    check_breaches = ui
+                        .add_enabled(!self.breach_list_path.trim().is_empty(), egui::Button::new("🔎 Fuites"))
+                        .on_hover_text("Comparaison locale: aucun mot de passe ni haché n'est envoyé")
+                        .clicked();
+                });
                 ui.add_space(10.0);
 
-                if ui.button("🎲 Générer").clicked() {
-                    match generate_password(&self.generator_options) {
-                        Ok(pwd) => {
-                            self.generated_password = pwd;
-                            self.update_activity();
-                        }
-                        Err(e) => {
-                            self.error_message = Some(e);
+                let master_reused = &self.master_reused_by;
+                let weak = vault.weak_entries();
+                let reused = vault.find_duplicate_passwords();
+                let shared_totp = vault.find_duplicate_totp();
+                let secret_notes = vault.notes_with_secrets();
+
+                if master_reused.is_empty()
+                    && weak.is_empty()
+                    && reused.is_empty()
+                    && shared_totp.is_empty()
+                    && secret_notes.is_empty()
+                {
+                    ui.label("✓ Aucun mot de passe faible ni réutilisé");
+                    return;
+                }
+
+                egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                    if !master_reused.is_empty() {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(220, 53, 69),
+                            format!("Mot de passe maître réutilisé ({})", master_reused.len()),
+                        );
+                        for id in master_reused {
+                            if let Some(entry) = vault.get_entry(*id) {
+                                if ui.link(format!("{} {}", entry.display_icon(), entry.name)).clicked() {
+                                    select = Some(*id);
+                                }
+                            }
                         }
+                        ui.add_space(10.0);
                     }
-                }
 
-                if !self.generated_password.is_empty() {
-                    ui.add_space(10.0);
-                    ui.label("Mot de passe généré:");
-                    
-                    let pwd_clone = self.generated_password.clone();
-                    ui.code(&pwd_clone);
+                    if !weak.is_empty() {
+                        ui.strong(format!("Mots de passe faibles ou moyens ({})", weak.len()));
+                        for id in &weak {
+                            if let Some(entry) = vault.get_entry(*id) {
+                                if ui.link(format!("{} {}", entry.display_icon(), entry.name)).clicked() {
+                                    select = Some(*id);
+                                }
+                            }
+                        }
+                        ui.add_space(10.0);
+                    }
 
-                    if ui.button("📋 Copier").clicked() {
-                        let pwd = self.generated_password.clone();
-                        self.copy_to_clipboard(&pwd);
-                        self.update_activity();
+                    if !reused.is_empty() {
+                        ui.strong(format!("Mots de passe réutilisés ({} groupes)", reused.len()));
+                        for group in &reused {
+                            ui.group(|ui| {
+                                for id in group {
+                                    if let Some(entry) = vault.get_entry(*id) {
+                                        if ui.link(format!("{} {}", entry.display_icon(), entry.name)).clicked() {
+                                            select = Some(*id);
+                                        }
+                                    }
+                                }
+                            });
+                        }
+                        ui.add_space(10.0);
                     }
 
-                    if ui.button("✓ Utiliser").clicked() {
-                        if let Some(entry) = &mut self.editing_entry {
-                            entry.password = self.generated_password.clone();
+                    if !shared_totp.is_empty() {
+                        ui.strong(format!("Secrets TOTP partagés ({} groupes)", shared_totp.len()))
+                            .on_hover_text("Un même secret sur plusieurs comptes vient souvent d'un import raté");
+                        for group in &shared_totp {
+                            ui.group(|ui| {
+                                for id in group {
+                                    if let Some(entry) = vault.get_entry(*id) {
+                                        if ui.link(format!("{} {}", entry.display_icon(), entry.name)).clicked() {
+                                            select = Some(*id);
+                                        }
+                                    }
+                                }
+                            });
                         }
-                        self.show_generator = false;
-                        self.update_activity();
+                        ui.add_space(10.0);
                     }
 
-                    let strength = estimate_strength(&self.generated_password);
-                    ui.horizontal(|ui| {
-                        ui.label("Force:");
-                        ui.colored_label(strength.color(), strength.label());
-                    });
-                }
+                    if !secret_notes.is_empty() {
+                        ui.strong(format!("Secrets possibles dans les notes ({})", secret_notes.len()))
+                            .on_hover_text(
+                                "Les notes ne sont pas masquées: déplacez ces valeurs dans le mot de passe, \
+                                 le TOTP ou des notes protégées",
+                            );
+                        for id in &secret_notes {
+                            if let Some(entry) = vault.get_entry(*id) {
+                                ui.horizontal(|ui| {
+                                    if ui.link(format!("{} {}", entry.display_icon(), entry.name)).clicked() {
+                                        select = Some(*id);
+                                    }
+                                    if ui.small_button("Ignorer").on_hover_text("Faux positif").clicked() {
+                                        dismiss_notes = Some(*id);
+                                    }
+                                });
+                            }
+                        }
+                    }
+                });
             });
 
-        if !open {
-            self.show_generator = false;
+        if let Some(id) = select {
+            self.selected_entry = Some(id);
         }
-    }
-
-    fn show_delete_confirmation(&mut self, ctx: &egui::Context) {
-        let mut open = true;
 
-        egui::Window::new("⚠️ Confirmation")
-            .open(&mut open)
-            .collapsible(false)
-            .show(ctx, |ui| {
-                ui.label("Voulez-vous vraiment supprimer cette entrée ?");
-                ui.label("Cette action est irréversible.");
+        if let Some(id) = dismiss_notes {
+            if let Some(entry) = self.vault.as_mut().and_then(|v| v.entries.iter_mut().find(|e| e.id == id)) {
+                entry.notes_warning_dismissed = true;
+                self.mark_dirty();
+            }
+        }
 
-                ui.add_space(20.0);
+        if check_breaches {
+            self.check_breaches();
+        }
 
-                if ui.button("🗑️ Supprimer").clicked() {
-                    if let Some(id) = self.confirm_delete {
-                        if let Some(vault) = &mut self.vault {
-                            if let Some(entry) = vault.get_entry(id) {
-                                let name = entry.name.clone();
-                                vault.delete_entry(id);
-                                self.add_audit(AuditAction::EntryDeleted(name));
-                                self.selected_entry = None;
-                                self.update_search();
-                                self.success_message = Some("Entrée supprimée".to_string());
-                            }
-                        }
-                    }
-                    self.confirm_delete = None;
-                    self.update_activity();
-                }
+        if !open {
+            self.show_health = false;
+        }
+    }
 
-                if ui.button("❌ Annuler").clicked() {
-                    self.confirm_delete = None;
-                    self.update_activity();
-                }
-            });
+    /// Met à jour le cache `breach_status` depuis la liste locale choisie
+    fn check_breaches(&mut self) {
+        let Some(vault) = &mut self.vault else {
+            return;
+        };
 
-        if !open {
-            self.confirm_delete = None;
+        match check_breaches_in_file(vault, &PathBuf::from(self.breach_list_path.trim())) {
+            Ok(0) => self.notify("Aucun mot de passe trouvé dans la liste de fuites".to_string()),
+            Ok(breached) => self.warn(format!("{} mot(s) de passe compromis", breached)),
+            Err(e) => {
+                self.error_message = Some(format!("Erreur lors de la vérification des fuites: {}", e));
+                return;
+            }
         }
+        self.mark_dirty();
     }
 
     fn show_audit_window(&mut self, ctx: &egui::Context) {
@@ -678,7 +5064,7 @@ impl PasswordManagerApp {
                 egui::ScrollArea::vertical().show(ui, |ui| {
                     for entry in self.audit_log.iter().rev() {
                         ui.horizontal(|ui| {
-                            ui.label(entry.timestamp.format("%Y-%m-%d %H:%M:%S").to_string());
+                            ui.label(self.settings.format_timestamp(&entry.timestamp));
                             ui.separator();
                             ui.label(entry.description());
                         });
@@ -690,12 +5076,31 @@ impl PasswordManagerApp {
             self.show_audit = false;
         }
     }
-}
 
-impl eframe::App for PasswordManagerApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    /// Efface les données déchiffrées avant qu'une panique ne soit signalée
+    fn wipe_secrets(&mut self) {
+        self.set_session(None);
+        // Le Drop des entrées efface leurs champs sensibles
+        self.vault = None;
+        self.editing_entry = None;
+        self.last_copied = None;
+        self.master_password.zeroize();
+        self.backup_password.zeroize();
+    }
+
+    /// Une image de l'interface; appelée par `update` sous la garde de panique
+    fn update_frame(&mut self, ctx: &egui::Context) {
+        self.track_input_activity(ctx);
         self.check_auto_lock();
+        #[cfg(feature = "global-hotkey")]
+        self.check_lock_hotkey();
+        #[cfg(feature = "deep-link")]
+        self.apply_deep_link();
+        self.check_reveal_all(ctx);
         self.check_clipboard_clear();
+        self.poll_pending_load();
+        self.autosave_if_due();
+        self.update_window_title(ctx);
 
         if let Some(msg) = &self.error_message.clone() {
             egui::Window::new("❌ Erreur")
@@ -708,15 +5113,8 @@ impl eframe::App for PasswordManagerApp {
                 });
         }
 
-        if let Some(msg) = &self.success_message.clone() {
-            egui::Window::new("✓ Succès")
-                .collapsible(false)
-                .show(ctx, |ui| {
-                    ui.label(msg);
-                    if ui.button("OK").clicked() {
-                        self.success_message = None;
-                    }
-                });
+        if self.show_clipboard_warning {
+            self.show_clipboard_warning_window(ctx);
         }
 
         egui::CentralPanel::default().show(ctx, |ui| {
@@ -729,6 +5127,135 @@ impl eframe::App for PasswordManagerApp {
             }
         });
 
-        ctx.request_repaint_after(Duration::from_secs(1));
+        self.show_toasts(ctx);
+
+        // Sans minuteur en cours, egui dort jusqu'à la prochaine saisie
+        if self.pending_load.is_some() {
+            ctx.request_repaint_after(Duration::from_millis(50));
+        } else if let Some(wakeup) = self.next_wakeup() {
+            ctx.request_repaint_after(wakeup);
+        }
+    }
+}
+
+/// Correspondance approximative: les caractères de la requête doivent apparaître dans l'ordre.
+/// Les suites consécutives et les débuts de mot sont favorisés; `None` si pas de correspondance.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = (position..candidate.len()).find(|&i| candidate[i] == q)?;
+        score += 1;
+        if previous.map_or(false, |p| p + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !candidate[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+
+    Some(score)
+}
+
+/// Choix du jeu de symboles; `id_source` distingue les fenêtres ouvertes en même temps
+fn symbol_preset_combo(ui: &mut egui::Ui, id_source: &str, options: &mut PasswordGeneratorOptions) {
+    ui.add_enabled_ui(options.include_symbols, |ui| {
+        egui::ComboBox::from_id_source(id_source)
+            .selected_text(options.symbol_set.label())
+            .show_ui(ui, |ui| {
+                for preset in SymbolPreset::ALL {
+                    ui.selectable_value(&mut options.symbol_set, preset, preset.label())
+                        .on_hover_text(preset.symbols());
+                }
+            });
+    });
+}
+
+/// Ligne min/max d'une classe de caractères dans les options avancées du générateur
+fn class_bounds_row(ui: &mut egui::Ui, label: &str, min: &mut usize, max: &mut Option<usize>) {
+    ui.label(label);
+    ui.add(egui::DragValue::new(min).range(0..=64));
+    ui.horizontal(|ui| {
+        let mut limited = max.is_some();
+        if ui.checkbox(&mut limited, "").changed() {
+            *max = if limited { Some((*min).max(1)) } else { None };
+        }
+        if let Some(max) = max {
+            ui.add(egui::DragValue::new(max).range(0..=64));
+        }
+    });
+    ui.end_row();
+}
+
+/// Barres horizontales du nombre d'identifiants par niveau de force
+fn show_strength_histogram(ui: &mut egui::Ui, counts: &[usize; 4]) {
+    let max = counts.iter().copied().max().unwrap_or(0).max(1);
+    egui::Grid::new("strength_histogram").num_columns(3).show(ui, |ui| {
+        for (strength, count) in PasswordStrength::ALL.iter().zip(counts) {
+            ui.label(strength.label());
+            let (rect, _) = ui.allocate_exact_size(egui::vec2(160.0, 12.0), egui::Sense::hover());
+            let width = rect.width() * *count as f32 / max as f32;
+            let bar = egui::Rect::from_min_size(rect.min, egui::vec2(width, rect.height()));
+            ui.painter().rect_filled(bar, 2.0, strength.color());
+            ui.label(count.to_string());
+            ui.end_row();
+        }
+    });
+}
+
+/// Libellé de la liste latérale: la partie du nom correspondant à la recherche est surlignée,
+/// et le champ ayant correspondu est indiqué quand ce n'est pas le nom
+fn entry_list_label(
+    ui: &egui::Ui,
+    entry: &Entry,
+    color: egui::Color32,
+    matched: Option<&MatchInfo>,
+    suffix: &str,
+) -> egui::text::LayoutJob {
+    let font_id = egui::TextStyle::Button.resolve(ui.style());
+    let normal = egui::TextFormat::simple(font_id.clone(), color);
+    let highlight = egui::TextFormat {
+        background: egui::Color32::from_rgb(255, 193, 7),
+        ..egui::TextFormat::simple(font_id.clone(), egui::Color32::BLACK)
+    };
+
+    let mut job = egui::text::LayoutJob::default();
+    job.append(&format!("{} ", entry.display_icon()), 0.0, normal.clone());
+
+    match matched {
+        Some(info) if info.field == MatchField::Name && !info.range.is_empty() => {
+            job.append(&entry.name[..info.range.start], 0.0, normal.clone());
+            job.append(&entry.name[info.range.clone()], 0.0, highlight);
+            job.append(&entry.name[info.range.end..], 0.0, normal.clone());
+        }
+        _ => job.append(&entry.name, 0.0, normal.clone()),
+    }
+
+    job.append(suffix, 0.0, normal);
+
+    if let Some(info) = matched.filter(|info| info.field != MatchField::Name) {
+        job.append(
+            &format!(" [{}]", info.field.label()),
+            0.0,
+            egui::TextFormat::simple(font_id, ui.visuals().weak_text_color()),
+        );
+    }
+
+    job
+}
+
+impl eframe::App for PasswordManagerApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        panic_guard::guard(self, Self::wipe_secrets, |app| app.update_frame(ctx));
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.save_now();
+        self.persist_generator_options();
     }
 }
\ No newline at end of file
@@ -0,0 +1,101 @@
+//! Outil en ligne de commande pour les scripts (sauvegardes automatisées, CI).
+//!
+//! Le mot de passe maître est demandé sans écho sur le terminal. Avec `--password-env`,
+//! il est lu dans la variable `MDP_MASTER`: pratique pour l'automatisation, mais une
+//! variable d'environnement peut fuiter (listes de processus, `/proc/<pid>/environ`,
+//! journaux de CI, processus enfants). L'interface graphique n'utilise jamais ce mécanisme.
+//!
+//! Codes de sortie: 0 succès, 1 erreur générale, 2 mot de passe incorrect,
+//! 3 fichier inaccessible, 4 fichier illisible ou format inattendu.
+
+use clap::{Parser, Subcommand};
+use mdp_manager::crypto::CryptoError;
+use mdp_manager::storage::{export_encrypted, open_vault_file, verify_master_password, StorageError};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use zeroize::Zeroize;
+
+const PASSWORD_ENV: &str = "MDP_MASTER";
+
+#[derive(Parser)]
+#[command(name = "mdp_cli", about = "Gestionnaire de mots de passe en ligne de commande")]
+struct Cli {
+    /// Lit le mot de passe maître dans la variable MDP_MASTER (risque de fuite, voir la doc)
+    #[arg(long, global = true)]
+    password_env: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Vérifie que le mot de passe ouvre le coffre, sans lire les entrées ni modifier le fichier
+    Verify { vault: PathBuf },
+    /// Affiche le nom des entrées
+    List { vault: PathBuf },
+    /// Exporte une sauvegarde chiffrée (.mdpx) protégée par le mot de passe maître
+    Backup { vault: PathBuf, output: PathBuf },
+}
+
+fn read_master_password(from_env: bool) -> Result<String, String> {
+    if !from_env {
+        return rpassword::prompt_password("Mot de passe maître: ").map_err(|e| e.to_string());
+    }
+
+    eprintln!(
+        "⚠️ Mot de passe lu depuis {}: les variables d'environnement peuvent fuiter via la liste des processus",
+        PASSWORD_ENV
+    );
+    let password = std::env::var(PASSWORD_ENV).map_err(|_| format!("Variable {} absente", PASSWORD_ENV))?;
+    // Ne pas la transmettre à d'éventuels processus enfants
+    std::env::remove_var(PASSWORD_ENV);
+    Ok(password)
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    let mut password = read_master_password(cli.password_env)?;
+
+    let result = match &cli.command {
+        Command::Verify { vault } => match verify_master_password(vault, &password) {
+            Ok(true) => {
+                println!("Mot de passe correct");
+                Ok(())
+            }
+            Ok(false) => Err(StorageError::Crypto(CryptoError::DecryptionFailed)),
+            Err(e) => Err(e),
+        },
+        Command::List { vault } => open_vault_file(vault, &password).map(|loaded| {
+            for entry in &loaded.vault.entries {
+                println!("{}", entry.name);
+            }
+        }),
+        Command::Backup { vault, output } => open_vault_file(vault, &password).and_then(|loaded| {
+            export_encrypted(&loaded.vault, output, &password)?;
+            println!("Sauvegarde écrite dans {}", output.display());
+            Ok(())
+        }),
+    };
+
+    password.zeroize();
+    Ok(result?)
+}
+
+fn exit_code(error: &StorageError) -> u8 {
+    match error {
+        e if e.is_wrong_password() => 2,
+        StorageError::Io(_) | StorageError::InvalidPath(..) => 3,
+        StorageError::Serialization(_) | StorageError::Format(_) => 4,
+        _ => 1,
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Erreur: {}", e);
+            ExitCode::from(e.downcast_ref::<StorageError>().map_or(1, exit_code))
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Presse-papiers avec effacement différé. Le presse-papiers système (arboard) est
+//! derrière `ClipboardBackend`: le cycle copie → effacement se teste sans serveur d'affichage.
+
+use sha2::{Digest, Sha256};
+use std::time::{Duration, Instant};
+use zeroize::Zeroize;
+
+/// Accès minimal au presse-papiers
+pub trait ClipboardBackend {
+    fn set_text(&mut self, text: &str) -> Result<(), String>;
+    fn get_text(&mut self) -> Result<String, String>;
+}
+
+/// Presse-papiers du système
+pub struct ArboardBackend(arboard::Clipboard);
+
+impl ArboardBackend {
+    /// `None` si aucun presse-papiers n'est accessible (pas de serveur d'affichage, portail Wayland absent…)
+    pub fn new() -> Option<Self> {
+        arboard::Clipboard::new().ok().map(Self)
+    }
+}
+
+impl ClipboardBackend for ArboardBackend {
+    fn set_text(&mut self, text: &str) -> Result<(), String> {
+        self.0.set_text(text).map_err(|e| e.to_string())
+    }
+
+    fn get_text(&mut self) -> Result<String, String> {
+        self.0.get_text().map_err(|e| e.to_string())
+    }
+}
+
+/// Résultat d'un effacement arrivé à échéance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClearOutcome {
+    Cleared,
+    /// Le contenu a changé depuis la copie (l'utilisateur a copié autre chose): laissé intact
+    Skipped,
+}
+
+/// Effacement programmé; seule l'empreinte du texte copié est conservée
+struct PendingClear {
+    at: Instant,
+    fingerprint: [u8; 32],
+}
+
+fn fingerprint(text: &str) -> [u8; 32] {
+    Sha256::digest(text.as_bytes()).into()
+}
+
+/// Presse-papiers de l'application: copie, puis effacement à l'échéance
+pub struct ClipboardManager {
+    backend: Option<Box<dyn ClipboardBackend>>,
+    pending: Option<PendingClear>,
+}
+
+impl ClipboardManager {
+    pub fn new(backend: Option<Box<dyn ClipboardBackend>>) -> Self {
+        Self { backend, pending: None }
+    }
+
+    /// Presse-papiers du système, s'il est accessible
+    pub fn system() -> Self {
+        Self::new(ArboardBackend::new().map(|b| Box::new(b) as Box<dyn ClipboardBackend>))
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.backend.is_some()
+    }
+
+    /// Copie `text` et programme son effacement à `now + delay`
+    pub fn copy(&mut self, text: &str, delay: Duration, now: Instant) -> Result<(), String> {
+        let backend = self.backend.as_mut().ok_or("Presse-papiers indisponible")?;
+        backend.set_text(text)?;
+        self.pending = Some(PendingClear {
+            at: now + delay,
+            fingerprint: fingerprint(text),
+        });
+        Ok(())
+    }
+
+    /// Échéance de l'effacement programmé, s'il y en a un
+    pub fn clear_deadline(&self) -> Option<Instant> {
+        self.pending.as_ref().map(|p| p.at)
+    }
+
+    /// Efface si l'échéance est passée et que le presse-papiers contient toujours notre copie
+    pub fn clear_if_due(&mut self, now: Instant) -> Option<ClearOutcome> {
+        if self.pending.as_ref().map_or(true, |p| now < p.at) {
+            return None;
+        }
+        let pending = self.pending.take()?;
+        let backend = self.backend.as_mut()?;
+
+        // Contenu illisible (image, presse-papiers vidé ailleurs): effacer par prudence
+        let unchanged = backend.get_text().map_or(true, |mut current| {
+            let same = fingerprint(&current) == pending.fingerprint;
+            current.zeroize();
+            same
+        });
+        if !unchanged {
+            return Some(ClearOutcome::Skipped);
+        }
+
+        let _ = backend.set_text("");
+        Some(ClearOutcome::Cleared)
+    }
+}
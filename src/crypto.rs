@@ -7,9 +7,21 @@ use argon2::{
     Argon2, Params, PasswordHasher, Version,
 };
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sharks::{Share, Sharks};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use unicode_normalization::UnicodeNormalization;
 use zeroize::Zeroize;
 
 pub const NONCE_SIZE: usize = 12; // 96 bits pour AES-GCM
+pub const KEY_SIZE: usize = 32; // 256 bits pour AES-256-GCM
+
+/// Longueurs de sortie d'Argon2 acceptées (128 à 512 bits)
+const MIN_KEY_SIZE: usize = 16;
+const MAX_KEY_SIZE: usize = 64;
 
 #[derive(Debug)]
 pub enum CryptoError {
@@ -17,6 +29,7 @@ pub enum CryptoError {
     DecryptionFailed,
     InvalidKey,
     KdfError(String),
+    InvalidShares,
 }
 
 impl std::fmt::Display for CryptoError {
@@ -26,12 +39,14 @@ impl std::fmt::Display for CryptoError {
             CryptoError::DecryptionFailed => write!(f, "Échec du déchiffrement (mot de passe incorrect?)"),
             CryptoError::InvalidKey => write!(f, "Clé invalide"),
             CryptoError::KdfError(e) => write!(f, "Erreur KDF: {}", e),
+            CryptoError::InvalidShares => write!(f, "Parts de clé invalides ou insuffisantes"),
         }
     }
 }
 
 impl std::error::Error for CryptoError {}
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CryptoParams {
     pub time_cost: u32,
     pub memory_cost: u32,
@@ -48,12 +63,83 @@ impl Default for CryptoParams {
     }
 }
 
-/// Dérive une clé de 256 bits depuis un mot de passe avec Argon2id
+/// Sous ce seuil de mémoire disponible, la mémoire d'Argon2 est réduite
+pub const LOW_MEMORY_THRESHOLD: u64 = 1024 * 1024 * 1024; // 1 GiB
+const MIN_MEMORY_COST: u32 = 8192; // 8 MiB
+
+impl CryptoParams {
+    /// Paramètres adaptés à la mémoire disponible (en octets): sous le seuil,
+    /// Argon2 n'utilise qu'un seizième de celle-ci, sans descendre sous 8 MiB.
+    pub fn for_available_memory(available_bytes: u64) -> Self {
+        let mut params = Self::default();
+        if available_bytes < LOW_MEMORY_THRESHOLD {
+            let budget_kib = (available_bytes / 16 / 1024) as u32;
+            params.memory_cost = budget_kib.clamp(MIN_MEMORY_COST, params.memory_cost);
+        }
+        params
+    }
+
+    /// Vrai si la dérivation est plus faible que les paramètres par défaut
+    pub fn is_reduced(&self) -> bool {
+        self.memory_cost < Self::default().memory_cost
+    }
+}
+
+/// Paramètres KDF pour les nouveaux coffres, selon la mémoire disponible de la machine
+pub fn detect_kdf_params() -> CryptoParams {
+    let mut system = sysinfo::System::new();
+    system.refresh_memory();
+    CryptoParams::for_available_memory(system.available_memory())
+}
+
+/// Dérive une clé de 256 bits depuis un mot de passe avec Argon2id.
+///
+/// Le mot de passe est normalisé en NFC avant hachage: une phrase accentuée saisie
+/// sous forme composée (Windows, Linux) ou décomposée (macOS) donne la même clé.
 pub fn derive_key(
     password: &str,
     salt: &[u8],
     params: &CryptoParams,
 ) -> Result<Vec<u8>, CryptoError> {
+    derive_key_with_len(password, salt, params, KEY_SIZE)
+}
+
+/// Comme `derive_key`, pour un algorithme dont la clé fait `key_len` octets
+pub fn derive_key_with_len(
+    password: &str,
+    salt: &[u8],
+    params: &CryptoParams,
+    key_len: usize,
+) -> Result<Vec<u8>, CryptoError> {
+    let mut normalized: String = password.nfc().collect();
+    let key = argon2_hash(normalized.as_bytes(), salt, params, key_len);
+    normalized.zeroize();
+    key
+}
+
+/// Dérivation d'avant la normalisation NFC, sur les octets tels que saisis.
+/// Sert uniquement à rouvrir les coffres créés avec une phrase décomposée.
+pub fn derive_key_unnormalized(
+    password: &str,
+    salt: &[u8],
+    params: &CryptoParams,
+) -> Result<Vec<u8>, CryptoError> {
+    argon2_hash(password.as_bytes(), salt, params, KEY_SIZE)
+}
+
+fn argon2_hash(
+    password: &[u8],
+    salt: &[u8],
+    params: &CryptoParams,
+    key_len: usize,
+) -> Result<Vec<u8>, CryptoError> {
+    if !(MIN_KEY_SIZE..=MAX_KEY_SIZE).contains(&key_len) {
+        return Err(CryptoError::KdfError(format!(
+            "Longueur de clé {} hors limites ({}-{} octets)",
+            key_len, MIN_KEY_SIZE, MAX_KEY_SIZE
+        )));
+    }
+
     let salt_string = SaltString::encode_b64(salt)
         .map_err(|e| CryptoError::KdfError(e.to_string()))?;
 
@@ -62,7 +148,7 @@ pub fn derive_key(
         params.memory_cost,
         params.time_cost,
         params.parallelism,
-        Some(32), // Output length: 32 bytes (256 bits)
+        Some(key_len),
     )
     .map_err(|e| CryptoError::KdfError(e.to_string()))?;
 
@@ -73,7 +159,7 @@ pub fn derive_key(
     );
 
     let password_hash = argon2
-        .hash_password(password.as_bytes(), &salt_string)
+        .hash_password(password, &salt_string)
         .map_err(|e| CryptoError::KdfError(e.to_string()))?;
 
     let hash = password_hash.hash
@@ -96,9 +182,16 @@ pub fn generate_nonce() -> Vec<u8> {
     nonce
 }
 
+/// Génère des octets aléatoires (bourrage indiscernable d'un chiffré)
+pub fn random_bytes(len: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; len];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
 /// Chiffre des données avec AES-256-GCM
 pub fn encrypt(data: &[u8], key: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CryptoError> {
-    if key.len() != 32 {
+    if key.len() != KEY_SIZE {
         return Err(CryptoError::InvalidKey);
     }
     if nonce.len() != NONCE_SIZE {
@@ -117,7 +210,7 @@ pub fn encrypt(data: &[u8], key: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CryptoE
 
 /// Déchiffre des données avec AES-256-GCM
 pub fn decrypt(ciphertext: &[u8], key: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CryptoError> {
-    if key.len() != 32 {
+    if key.len() != KEY_SIZE {
         return Err(CryptoError::InvalidKey);
     }
     if nonce.len() != NONCE_SIZE {
@@ -134,19 +227,79 @@ pub fn decrypt(ciphertext: &[u8], key: &[u8], nonce: &[u8]) -> Result<Vec<u8>, C
         .map_err(|_| CryptoError::DecryptionFailed)
 }
 
-/// Wrapper sécurisé pour la clé de chiffrement (zeroize on drop)
+/// Verrouille en RAM les pages couvrant `data` (mlock sous Unix, VirtualLock sous Windows)
+/// pour éviter qu'elles partent dans le swap. Au mieux: `None` si l'OS refuse
+/// (limite RLIMIT_MEMLOCK atteinte, droits insuffisants, plateforme non supportée).
+/// Ne protège ni contre l'hibernation ni contre un dump mémoire d'un processus privilégié.
+fn lock_pages(data: &[u8]) -> Option<PageLock> {
+    if data.is_empty() {
+        return None;
+    }
+
+    let page = region::page::size();
+    let first = region::page::floor(data.as_ptr()) as usize;
+    let count = (data.as_ptr() as usize + data.len() - first).div_ceil(page);
+
+    // Tenu pendant l'appel système: compteurs et état réel des pages restent cohérents
+    let mut locked = LOCKED_PAGES.lock().unwrap_or_else(|e| e.into_inner());
+    // Le déverrouillage est géré par `PageLock`, page par page
+    std::mem::forget(region::lock(first as *const u8, count * page).ok()?);
+    for i in 0..count {
+        *locked.entry(first + i * page).or_insert(0) += 1;
+    }
+
+    Some(PageLock { first, count })
+}
+
+/// Tampons verrouillés par page: munlock n'est pas cumulatif, une page partagée par
+/// deux tampons ne doit être déverrouillée qu'à la libération du dernier
+static LOCKED_PAGES: Mutex<BTreeMap<usize, usize>> = Mutex::new(BTreeMap::new());
+
+/// Pages couvertes par un tampon verrouillé
+struct PageLock {
+    first: usize,
+    count: usize,
+}
+
+impl Drop for PageLock {
+    fn drop(&mut self) {
+        let page = region::page::size();
+        let mut locked = LOCKED_PAGES.lock().unwrap_or_else(|e| e.into_inner());
+        for i in 0..self.count {
+            let addr = self.first + i * page;
+            let Some(users) = locked.get_mut(&addr) else {
+                continue;
+            };
+            *users -= 1;
+            if *users == 0 {
+                locked.remove(&addr);
+                let _ = region::unlock(addr as *const u8, page);
+            }
+        }
+    }
+}
+
+/// Wrapper sécurisé pour la clé de chiffrement (zeroize on drop, pages verrouillées si possible)
 pub struct SecureKey {
     key: Vec<u8>,
+    // Déclaré après `key`: déverrouillé seulement une fois la clé effacée
+    lock: Option<PageLock>,
 }
 
 impl SecureKey {
     pub fn new(key: Vec<u8>) -> Self {
-        Self { key }
+        let lock = lock_pages(&key);
+        Self { key, lock }
     }
 
     pub fn as_bytes(&self) -> &[u8] {
         &self.key
     }
+
+    /// Vrai si l'OS a accepté de garder la clé hors du swap
+    pub fn is_locked(&self) -> bool {
+        self.lock.is_some()
+    }
 }
 
 impl Drop for SecureKey {
@@ -155,6 +308,124 @@ impl Drop for SecureKey {
     }
 }
 
+/// Tampon pour le clair du coffre déchiffré: mêmes garanties que `SecureKey`
+pub struct SecureBuffer {
+    data: Vec<u8>,
+    lock: Option<PageLock>,
+}
+
+impl SecureBuffer {
+    pub fn new(data: Vec<u8>) -> Self {
+        let lock = lock_pages(&data);
+        Self { data, lock }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.lock.is_some()
+    }
+}
+
+impl Drop for SecureBuffer {
+    fn drop(&mut self) {
+        self.data.zeroize();
+    }
+}
+
+/// Empreinte du mot de passe maître conservée en mémoire pour détecter sa réutilisation.
+/// HMAC-SHA256 sous une clé aléatoire propre à la session: assez bon marché pour être
+/// comparé à chaque entrée au déverrouillage, et seules des comparaisons sont possibles.
+pub struct MasterFingerprint {
+    key: SecureKey,
+    hash: SecureKey,
+}
+
+impl MasterFingerprint {
+    pub fn new(master_password: &str) -> Result<Self, CryptoError> {
+        let key = SecureKey::new(random_bytes(KEY_SIZE));
+        let hash = Self::digest(&key, master_password)?;
+        Ok(Self {
+            key,
+            hash: SecureKey::new(hash),
+        })
+    }
+
+    fn digest(key: &SecureKey, password: &str) -> Result<Vec<u8>, CryptoError> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes()).map_err(|_| CryptoError::InvalidKey)?;
+        // Même forme normalisée que la dérivation de clé
+        let mut normalized: String = password.nfc().collect();
+        mac.update(normalized.as_bytes());
+        normalized.zeroize();
+        Ok(mac.finalize().into_bytes().to_vec())
+    }
+
+    pub fn matches(&self, candidate: &str) -> bool {
+        if candidate.is_empty() {
+            return false;
+        }
+        match Self::digest(&self.key, candidate) {
+            Ok(mut hash) => {
+                let same = hash == self.hash.as_bytes();
+                hash.zeroize();
+                same
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+/// Préfixe d'une part de clé: `mdp-share:<seuil>:<part en base64>`
+const SHARE_PREFIX: &str = "mdp-share";
+
+/// Découpe une clé en `n` parts dont `k` suffisent à la reconstituer (partage de Shamir).
+/// Renvoie une liste vide si `k` vaut 0 ou dépasse `n`.
+pub fn split_key(key: &[u8], n: u8, k: u8) -> Vec<String> {
+    if k == 0 || k > n {
+        return Vec::new();
+    }
+
+    Sharks(k)
+        .dealer(key)
+        .take(n as usize)
+        .map(|share| {
+            let mut bytes = Vec::from(&share);
+            let encoded = format!("{}:{}:{}", SHARE_PREFIX, k, encode_base64(&bytes));
+            bytes.zeroize();
+            encoded
+        })
+        .collect()
+}
+
+/// Reconstitue une clé à partir d'au moins `k` parts produites par `split_key`
+pub fn combine_key(shares: &[String]) -> Result<Vec<u8>, CryptoError> {
+    let mut threshold = None;
+    let mut parsed = Vec::with_capacity(shares.len());
+
+    for share in shares {
+        let mut parts = share.trim().splitn(3, ':');
+        let (Some(SHARE_PREFIX), Some(k), Some(data)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(CryptoError::InvalidShares);
+        };
+        let k: u8 = k.parse().map_err(|_| CryptoError::InvalidShares)?;
+        if *threshold.get_or_insert(k) != k {
+            return Err(CryptoError::InvalidShares);
+        }
+
+        let mut bytes = decode_base64(data).map_err(|_| CryptoError::InvalidShares)?;
+        let share = Share::try_from(bytes.as_slice()).map_err(|_| CryptoError::InvalidShares);
+        bytes.zeroize();
+        parsed.push(share?);
+    }
+
+    let threshold = threshold.ok_or(CryptoError::InvalidShares)?;
+    Sharks(threshold)
+        .recover(&parsed)
+        .map_err(|_| CryptoError::InvalidShares)
+}
+
 /// Encode en base64
 pub fn encode_base64(data: &[u8]) -> String {
     BASE64.encode(data)
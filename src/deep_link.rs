@@ -0,0 +1,38 @@
+//! Liens `mdp://entry/<uuid>` par lesquels un script ou une extension de navigateur
+//! demande à l'application d'afficher une entrée. Le système les transmet en argument
+//! de la ligne de commande une fois le schéma `mdp` associé à l'exécutable.
+
+use uuid::Uuid;
+
+pub const SCHEME: &str = "mdp";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeepLink {
+    /// Sélectionner l'entrée, après déverrouillage si besoin
+    Entry(Uuid),
+}
+
+/// Analyse un lien; `None` s'il est inconnu ou mal formé, pour qu'il soit simplement ignoré
+pub fn parse(uri: &str) -> Option<DeepLink> {
+    let url = url::Url::parse(uri.trim()).ok()?;
+    if url.scheme() != SCHEME {
+        return None;
+    }
+
+    match url.host_str()? {
+        "entry" => {
+            // Une barre oblique finale est tolérée, pas un chemin plus long
+            let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+            match segments.as_slice() {
+                [id] => Uuid::parse_str(id).ok().map(DeepLink::Entry),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Premier lien reconnu parmi les arguments de la ligne de commande
+pub fn from_args(args: impl IntoIterator<Item = String>) -> Option<DeepLink> {
+    args.into_iter().find_map(|arg| parse(&arg))
+}
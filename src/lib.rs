@@ -0,0 +1,13 @@
+//! Cœur du gestionnaire (chiffrement, stockage, modèles), partagé par l'interface graphique,
+//! l'outil en ligne de commande et les tests
+
+pub mod clipboard;
+pub mod crypto;
+#[cfg(feature = "deep-link")]
+pub mod deep_link;
+pub mod models;
+pub mod password_generator;
+pub mod settings;
+pub mod storage;
+pub mod totp;
+pub mod vault_storage;
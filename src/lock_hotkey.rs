@@ -0,0 +1,46 @@
+//! Raccourci système de verrouillage, actif même quand la fenêtre n'a pas le focus
+//! (fonctionnalité `global-hotkey`)
+
+use global_hotkey::hotkey::HotKey;
+use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+
+/// Raccourci enregistré auprès du système; libéré à la destruction
+pub struct LockHotkey {
+    manager: GlobalHotKeyManager,
+    hotkey: HotKey,
+}
+
+impl LockHotkey {
+    /// Enregistre `binding` (ex. "Ctrl+Alt+L"). L'erreur est un message lisible:
+    /// raccourci mal formé ou déjà pris par une autre application.
+    pub fn register(binding: &str) -> Result<Self, String> {
+        let hotkey: HotKey = binding
+            .trim()
+            .parse()
+            .map_err(|e| format!("Raccourci « {} » invalide: {}", binding, e))?;
+        let manager = GlobalHotKeyManager::new()
+            .map_err(|e| format!("Raccourcis globaux indisponibles: {}", e))?;
+        manager
+            .register(hotkey)
+            .map_err(|e| format!("Raccourci « {} » déjà utilisé ou refusé: {}", binding, e))?;
+
+        Ok(Self { manager, hotkey })
+    }
+
+    /// Vide la file d'événements; vrai si le raccourci a été pressé depuis le dernier appel
+    pub fn pressed(&self) -> bool {
+        let mut pressed = false;
+        while let Ok(event) = GlobalHotKeyEvent::receiver().try_recv() {
+            if event.id == self.hotkey.id() && event.state == HotKeyState::Pressed {
+                pressed = true;
+            }
+        }
+        pressed
+    }
+}
+
+impl Drop for LockHotkey {
+    fn drop(&mut self) {
+        let _ = self.manager.unregister(self.hotkey);
+    }
+}
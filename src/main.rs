@@ -1,14 +1,20 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+// Seule l'interface est propre au binaire; le cœur vient de la bibliothèque `mdp_manager`
 mod app;
-mod crypto;
-mod models;
-mod password_generator;
-mod storage;
+#[cfg(feature = "global-hotkey")]
+mod lock_hotkey;
+mod panic_guard;
 
-use app::PasswordManagerApp;
+use app::{PasswordManagerApp, APP_TITLE};
+#[cfg(feature = "deep-link")]
+use mdp_manager::deep_link;
+use std::sync::{Arc, Mutex};
 
 fn main() -> eframe::Result<()> {
+    let session = Arc::new(Mutex::new(None));
+    panic_guard::install(session.clone());
+
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([1024.0, 768.0])
@@ -17,8 +23,14 @@ fn main() -> eframe::Result<()> {
     };
 
     eframe::run_native(
-        "Gestionnaire de Mots de Passe",
+        APP_TITLE,
         native_options,
-        Box::new(|cc| Ok(Box::new(PasswordManagerApp::new(cc)))),
+        Box::new(|cc| {
+            #[allow(unused_mut)]
+            let mut app = PasswordManagerApp::new(cc, session);
+            #[cfg(feature = "deep-link")]
+            app.handle_deep_link(deep_link::from_args(std::env::args().skip(1)));
+            Ok(Box::new(app))
+        }),
     )
 }
\ No newline at end of file
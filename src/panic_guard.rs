@@ -0,0 +1,80 @@
+use mdp_manager::settings::config_dir;
+use mdp_manager::storage::VaultSession;
+use std::cell::{Cell, RefCell};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::sync::{Arc, Mutex, TryLockError};
+
+const CRASH_LOG_FILE: &str = "crash.log";
+
+/// Clé de session partagée entre l'application et le hook de panique
+pub type SharedSession = Arc<Mutex<Option<VaultSession>>>;
+
+thread_local! {
+    /// Une `guard` est active sur ce fil: le rapport attend que les secrets soient effacés
+    static GUARDED: Cell<bool> = const { Cell::new(false) };
+    static DEFERRED_REPORT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Installe un hook qui efface la clé de session avant le gestionnaire par défaut.
+/// Sous une `guard`, le rapport n'est affiché qu'après l'effacement du coffre déchiffré.
+pub fn install(session: SharedSession) {
+    let default_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        // try_lock: le verrou peut être tenu par le code qui a paniqué
+        match session.try_lock() {
+            Ok(mut guard) => drop(guard.take()),
+            Err(TryLockError::Poisoned(poisoned)) => drop(poisoned.into_inner().take()),
+            Err(TryLockError::WouldBlock) => {}
+        }
+
+        write_crash_log(info);
+
+        if GUARDED.with(Cell::get) {
+            let thread = std::thread::current();
+            let report = format!("thread '{}' {}", thread.name().unwrap_or("<unnamed>"), info);
+            DEFERRED_REPORT.with(|deferred| *deferred.borrow_mut() = Some(report));
+        } else {
+            default_hook(info);
+        }
+    }));
+}
+
+/// Exécute `run`; s'il panique, `wipe` efface les secrets de `state` avant que la panique
+/// ne soit signalée puis propagée (et avant tout abandon à une frontière FFI)
+pub fn guard<T>(state: &mut T, wipe: impl FnOnce(&mut T), run: impl FnOnce(&mut T)) {
+    let was_guarded = GUARDED.with(|guarded| guarded.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| run(&mut *state)));
+    GUARDED.with(|guarded| guarded.set(was_guarded));
+
+    if let Err(payload) = result {
+        wipe(state);
+        if let Some(report) = DEFERRED_REPORT.with(|deferred| deferred.borrow_mut().take()) {
+            eprintln!("{}", report);
+        }
+        panic::resume_unwind(payload);
+    }
+}
+
+/// Journal minimal: date et emplacement uniquement, jamais le message (qui pourrait contenir des données)
+fn write_crash_log(info: &PanicHookInfo<'_>) {
+    let Some(dir) = config_dir() else {
+        return;
+    };
+
+    let location = info
+        .location()
+        .map(|l| format!("{}:{}", l.file(), l.line()))
+        .unwrap_or_else(|| "inconnu".to_string());
+
+    let _ = std::fs::create_dir_all(&dir);
+    if let Ok(mut file) = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(CRASH_LOG_FILE))
+    {
+        let _ = writeln!(file, "{} panique à {}", chrono::Utc::now().to_rfc3339(), location);
+    }
+}
@@ -1,5 +1,8 @@
+use rand::seq::SliceRandom;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PasswordGeneratorOptions {
     pub length: usize,
     pub include_uppercase: bool,
@@ -7,6 +10,25 @@ pub struct PasswordGeneratorOptions {
     pub include_numbers: bool,
     pub include_symbols: bool,
     pub avoid_ambiguous: bool,
+    // Contraintes par classe (0 / None = aucune)
+    #[serde(default)]
+    pub min_uppercase: usize,
+    #[serde(default)]
+    pub min_lowercase: usize,
+    #[serde(default)]
+    pub min_digits: usize,
+    #[serde(default)]
+    pub min_symbols: usize,
+    #[serde(default)]
+    pub max_uppercase: Option<usize>,
+    #[serde(default)]
+    pub max_lowercase: Option<usize>,
+    #[serde(default)]
+    pub max_digits: Option<usize>,
+    #[serde(default)]
+    pub max_symbols: Option<usize>,
+    #[serde(default)]
+    pub symbol_set: SymbolPreset,
 }
 
 impl Default for PasswordGeneratorOptions {
@@ -18,6 +40,46 @@ impl Default for PasswordGeneratorOptions {
             include_numbers: true,
             include_symbols: true,
             avoid_ambiguous: true,
+            min_uppercase: 0,
+            min_lowercase: 0,
+            min_digits: 0,
+            min_symbols: 0,
+            max_uppercase: None,
+            max_lowercase: None,
+            max_digits: None,
+            max_symbols: None,
+            symbol_set: SymbolPreset::default(),
+        }
+    }
+}
+
+/// Jeux de symboles prédéfinis, pour les claviers où certains symboles sont pénibles à taper
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum SymbolPreset {
+    #[default]
+    Standard,
+    /// Symboles accessibles sans AltGr sur un clavier AZERTY français
+    Azerty,
+    /// Symboles présents sur le premier écran de symboles des claviers mobiles
+    Mobile,
+}
+
+impl SymbolPreset {
+    pub const ALL: [SymbolPreset; 3] = [SymbolPreset::Standard, SymbolPreset::Azerty, SymbolPreset::Mobile];
+
+    pub fn label(&self) -> &str {
+        match self {
+            SymbolPreset::Standard => "Tous les symboles",
+            SymbolPreset::Azerty => "Clavier facile (AZERTY)",
+            SymbolPreset::Mobile => "Clavier facile (mobile)",
+        }
+    }
+
+    pub fn symbols(&self) -> &'static str {
+        match self {
+            SymbolPreset::Standard => SYMBOLS,
+            SymbolPreset::Azerty => "&(-_)=,;:!*$",
+            SymbolPreset::Mobile => "!@#$%&*?-_",
         }
     }
 }
@@ -28,43 +90,180 @@ const NUMBERS: &str = "0123456789";
 const SYMBOLS: &str = "!@#$%^&*()_+-=[]{}|;:,.<>?";
 const AMBIGUOUS: &str = "il1Lo0O";
 
+/// Une classe de caractères retenue avec ses bornes
+struct CharClass {
+    chars: Vec<char>,
+    min: usize,
+    max: Option<usize>,
+}
+
+fn char_classes(options: &PasswordGeneratorOptions) -> Result<Vec<CharClass>, String> {
+    let candidates = [
+        ("majuscules", UPPERCASE, options.include_uppercase, options.min_uppercase, options.max_uppercase),
+        ("minuscules", LOWERCASE, options.include_lowercase, options.min_lowercase, options.max_lowercase),
+        ("chiffres", NUMBERS, options.include_numbers, options.min_digits, options.max_digits),
+        ("symboles", options.symbol_set.symbols(), options.include_symbols, options.min_symbols, options.max_symbols),
+    ];
+
+    let mut classes = Vec::new();
+
+    for (label, set, included, min, max) in candidates {
+        if !included {
+            if min > 0 {
+                return Err(format!("Minimum de {} demandé mais la classe est désactivée", label));
+            }
+            continue;
+        }
+
+        if max.map_or(false, |max| min > max) {
+            return Err(format!("Minimum de {} supérieur au maximum", label));
+        }
+
+        // Retirer les caractères ambigus si demandé
+        let chars: Vec<char> = set
+            .chars()
+            .filter(|c| !options.avoid_ambiguous || !AMBIGUOUS.contains(*c))
+            .collect();
+
+        classes.push(CharClass { chars, min, max });
+    }
+
+    Ok(classes)
+}
+
 pub fn generate_password(options: &PasswordGeneratorOptions) -> Result<String, String> {
     if options.length == 0 {
         return Err("La longueur doit être > 0".to_string());
     }
 
-    let mut charset = String::new();
+    let classes = char_classes(options)?;
 
-    if options.include_uppercase {
-        charset.push_str(UPPERCASE);
-    }
-    if options.include_lowercase {
-        charset.push_str(LOWERCASE);
+    if classes.is_empty() {
+        return Err("Au moins un type de caractère doit être sélectionné".to_string());
     }
-    if options.include_numbers {
-        charset.push_str(NUMBERS);
+
+    let required: usize = classes.iter().map(|c| c.min).sum();
+    if required > options.length {
+        return Err(format!(
+            "Les minimums ({}) dépassent la longueur ({})",
+            required, options.length
+        ));
     }
-    if options.include_symbols {
-        charset.push_str(SYMBOLS);
+
+    let mut rng = rand::thread_rng();
+    let mut password: Vec<char> = Vec::with_capacity(options.length);
+    let mut counts = vec![0usize; classes.len()];
+
+    // Placer d'abord les minimums requis
+    for (i, class) in classes.iter().enumerate() {
+        for _ in 0..class.min {
+            password.push(class.chars[rng.gen_range(0..class.chars.len())]);
+        }
+        counts[i] = class.min;
     }
 
-    if charset.is_empty() {
-        return Err("Au moins un type de caractère doit être sélectionné".to_string());
+    // Compléter avec les classes qui n'ont pas atteint leur maximum
+    while password.len() < options.length {
+        let available: Vec<usize> = (0..classes.len())
+            .filter(|&i| classes[i].max.map_or(true, |max| counts[i] < max))
+            .collect();
+
+        let pool_size: usize = available.iter().map(|&i| classes[i].chars.len()).sum();
+        if pool_size == 0 {
+            return Err("Les maximums ne permettent pas d'atteindre la longueur".to_string());
+        }
+
+        let mut pick = rng.gen_range(0..pool_size);
+        for &i in &available {
+            let class = &classes[i];
+            if pick < class.chars.len() {
+                password.push(class.chars[pick]);
+                counts[i] += 1;
+                break;
+            }
+            pick -= class.chars.len();
+        }
     }
 
-    // Retirer les caractères ambigus si demandé
-    if options.avoid_ambiguous {
-        charset.retain(|c| !AMBIGUOUS.contains(c));
+    password.shuffle(&mut rng);
+
+    Ok(password.into_iter().collect())
+}
+
+pub fn generate_many(
+    options: &PasswordGeneratorOptions,
+    count: usize,
+) -> Result<Vec<String>, String> {
+    (0..count).map(|_| generate_password(options)).collect()
+}
+
+/// Forme des identifiants générés
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UsernameStyle {
+    /// Pseudo aléatoire: une lettre puis des lettres minuscules et chiffres ("k3v9qz7m2a")
+    Random,
+    /// Deux mots et un nombre ("blue-otter-42")
+    Words,
+}
+
+impl UsernameStyle {
+    pub fn label(&self) -> &str {
+        match self {
+            UsernameStyle::Random => "Aléatoire",
+            UsernameStyle::Words => "Mots",
+        }
     }
+}
+
+const USERNAME_LENGTH: usize = 10;
+
+const USERNAME_ADJECTIVES: &[&str] = &[
+    "blue", "calm", "brave", "quick", "silent", "golden", "lucky", "bright", "wild", "gentle",
+    "swift", "clever", "sunny", "misty", "bold", "happy",
+];
+
+const USERNAME_NOUNS: &[&str] = &[
+    "otter", "falcon", "maple", "river", "comet", "badger", "willow", "harbor", "lynx", "pebble",
+    "cedar", "heron", "meadow", "fox", "glacier", "panda",
+];
 
-    let charset: Vec<char> = charset.chars().collect();
+pub fn generate_username(style: UsernameStyle) -> String {
     let mut rng = rand::thread_rng();
 
-    let password: String = (0..options.length)
-        .map(|_| charset[rng.gen_range(0..charset.len())])
-        .collect();
+    match style {
+        UsernameStyle::Random => {
+            let letters: Vec<char> = LOWERCASE.chars().collect();
+            let alphanumeric: Vec<char> = LOWERCASE.chars().chain(NUMBERS.chars()).collect();
+
+            // Commencer par une lettre: certains services refusent un chiffre en tête
+            let mut username = String::with_capacity(USERNAME_LENGTH);
+            username.push(*letters.choose(&mut rng).unwrap_or(&'a'));
+            for _ in 1..USERNAME_LENGTH {
+                username.push(*alphanumeric.choose(&mut rng).unwrap_or(&'a'));
+            }
+            username
+        }
+        UsernameStyle::Words => {
+            let adjective = USERNAME_ADJECTIVES.choose(&mut rng).unwrap_or(&"blue");
+            let noun = USERNAME_NOUNS.choose(&mut rng).unwrap_or(&"otter");
+            format!("{}-{}-{}", adjective, noun, rng.gen_range(10..100))
+        }
+    }
+}
+
+/// Entropie en bits d'un mot de passe généré avec ces options: longueur × log2(taille de l'alphabet).
+/// Mesure plus honnête que `estimate_strength`, qui ne juge que le résultat.
+pub fn options_entropy_bits(options: &PasswordGeneratorOptions) -> f64 {
+    let charset_size: usize = match char_classes(options) {
+        Ok(classes) => classes.iter().map(|c| c.chars.len()).sum(),
+        Err(_) => 0,
+    };
+
+    if charset_size == 0 {
+        return 0.0;
+    }
 
-    Ok(password)
+    options.length as f64 * (charset_size as f64).log2()
 }
 
 pub fn estimate_strength(password: &str) -> PasswordStrength {
@@ -99,6 +298,26 @@ pub enum PasswordStrength {
 }
 
 impl PasswordStrength {
+    /// Du plus faible au plus fort
+    pub const ALL: [PasswordStrength; 4] = [
+        PasswordStrength::Weak,
+        PasswordStrength::Medium,
+        PasswordStrength::Strong,
+        PasswordStrength::VeryStrong,
+    ];
+
+    pub fn from_entropy_bits(bits: f64) -> Self {
+        if bits < 40.0 {
+            PasswordStrength::Weak
+        } else if bits < 60.0 {
+            PasswordStrength::Medium
+        } else if bits < 80.0 {
+            PasswordStrength::Strong
+        } else {
+            PasswordStrength::VeryStrong
+        }
+    }
+
     pub fn label(&self) -> &str {
         match self {
             PasswordStrength::Weak => "Faible",
@@ -0,0 +1,302 @@
+use crate::models::TemplateEscape;
+use crate::password_generator::PasswordGeneratorOptions;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local, TimeZone, Utc};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SETTINGS_FILE: &str = "settings.json";
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// Fuseau utilisé pour afficher les dates
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum TimeDisplay {
+    #[default]
+    Local,
+    Utc,
+}
+
+impl TimeDisplay {
+    pub fn label(&self) -> &str {
+        match self {
+            TimeDisplay::Local => "Heure locale",
+            TimeDisplay::Utc => "UTC",
+        }
+    }
+}
+
+/// Modèle de « copie au format » (voir `models::format_entry`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CopyTemplate {
+    pub name: String,
+    pub template: String,
+    #[serde(default)]
+    pub escape: TemplateEscape,
+}
+
+/// Préférences de l'application (non sensibles, stockées en clair)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    #[serde(default = "default_generator_presets")]
+    pub generator_presets: Vec<(String, PasswordGeneratorOptions)>,
+    #[serde(default)]
+    pub clipboard_warning_dismissed: bool,
+    #[serde(default)]
+    pub time_display: TimeDisplay,
+    #[serde(default = "default_date_format")]
+    pub date_format: String,
+    /// Seuils d'ancienneté des mots de passe (jours)
+    #[serde(default = "default_age_warning_days")]
+    pub age_warning_days: i64,
+    #[serde(default = "default_age_critical_days")]
+    pub age_critical_days: i64,
+    /// Derniers réglages du générateur, restaurés au lancement
+    #[serde(default)]
+    pub generator_options: PasswordGeneratorOptions,
+    /// Durée maximale de l'affichage de tous les mots de passe (secondes)
+    #[serde(default = "default_reveal_all_seconds")]
+    pub reveal_all_seconds: u64,
+    /// Afficher une notification quand le presse-papiers est effacé
+    #[serde(default = "default_notify_clipboard_cleared")]
+    pub notify_clipboard_cleared: bool,
+    /// Assistant de premier lancement terminé ou ignoré. Absent d'un fichier
+    /// existant = installation antérieure à l'assistant, considérée comme terminée.
+    #[serde(default = "default_onboarding_completed")]
+    pub onboarding_completed: bool,
+    /// Raccourci système de verrouillage (fonctionnalité `global-hotkey`)
+    #[serde(default = "default_lock_hotkey")]
+    pub lock_hotkey: String,
+    /// Suivre les liens `mdp://entry/…` reçus au lancement (fonctionnalité `deep-link`)
+    #[serde(default)]
+    pub deep_links_enabled: bool,
+    /// Modèles proposés par « Copier au format » dans la fiche d'une entrée
+    #[serde(default = "default_copy_templates")]
+    pub copy_templates: Vec<CopyTemplate>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            generator_presets: default_generator_presets(),
+            clipboard_warning_dismissed: false,
+            time_display: TimeDisplay::default(),
+            date_format: default_date_format(),
+            age_warning_days: default_age_warning_days(),
+            age_critical_days: default_age_critical_days(),
+            generator_options: PasswordGeneratorOptions::default(),
+            reveal_all_seconds: default_reveal_all_seconds(),
+            notify_clipboard_cleared: default_notify_clipboard_cleared(),
+            onboarding_completed: false,
+            lock_hotkey: default_lock_hotkey(),
+            deep_links_enabled: false,
+            copy_templates: default_copy_templates(),
+        }
+    }
+}
+
+fn default_age_warning_days() -> i64 {
+    90
+}
+
+fn default_age_critical_days() -> i64 {
+    180
+}
+
+fn default_reveal_all_seconds() -> u64 {
+    30
+}
+
+fn default_notify_clipboard_cleared() -> bool {
+    true
+}
+
+fn default_onboarding_completed() -> bool {
+    true
+}
+
+fn default_lock_hotkey() -> String {
+    "Ctrl+Alt+L".to_string()
+}
+
+fn default_copy_templates() -> Vec<CopyTemplate> {
+    vec![
+        CopyTemplate {
+            name: ".netrc".to_string(),
+            template: "machine {host} login {login} password {password}".to_string(),
+            escape: TemplateEscape::None,
+        },
+        CopyTemplate {
+            name: "Variables shell".to_string(),
+            template: "USER={login} PASSWORD={password}".to_string(),
+            escape: TemplateEscape::Shell,
+        },
+    ]
+}
+
+fn default_date_format() -> String {
+    DEFAULT_DATE_FORMAT.to_string()
+}
+
+/// Formate une date dans le fuseau donné; un format invalide retombe sur le format par défaut
+pub fn format_timestamp_in<Tz: TimeZone>(dt: &DateTime<Utc>, tz: &Tz, format: &str) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let format = if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        DEFAULT_DATE_FORMAT
+    } else {
+        format
+    };
+
+    dt.with_timezone(tz).format(format).to_string()
+}
+
+fn default_generator_presets() -> Vec<(String, PasswordGeneratorOptions)> {
+    vec![
+        (
+            "Fort 20".to_string(),
+            PasswordGeneratorOptions {
+                length: 20,
+                ..Default::default()
+            },
+        ),
+        (
+            "PIN banque".to_string(),
+            PasswordGeneratorOptions {
+                length: 6,
+                include_uppercase: false,
+                include_lowercase: false,
+                include_numbers: true,
+                include_symbols: false,
+                avoid_ambiguous: false,
+                ..Default::default()
+            },
+        ),
+        (
+            "Sans symboles".to_string(),
+            PasswordGeneratorOptions {
+                length: 16,
+                include_symbols: false,
+                ..Default::default()
+            },
+        ),
+    ]
+}
+
+const DEFAULT_VAULT_FILE: &str = "default.vault";
+
+/// Fichier marqueur du mode portable, à placer à côté de l'exécutable
+const PORTABLE_MARKER: &str = "portable.txt";
+const PORTABLE_DATA_DIR: &str = "mdp_data";
+
+fn project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from("", "", "mdp_manager")
+}
+
+/// Répertoire de données du mode portable (clé USB), actif si `portable.txt`
+/// est présent à côté de l'exécutable
+pub fn portable_dir() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    portable_dir_in(exe.parent()?)
+}
+
+pub fn portable_dir_in(exe_dir: &Path) -> Option<PathBuf> {
+    exe_dir
+        .join(PORTABLE_MARKER)
+        .is_file()
+        .then(|| exe_dir.join(PORTABLE_DATA_DIR))
+}
+
+/// Répertoire de configuration de l'application
+pub fn config_dir() -> Option<PathBuf> {
+    portable_dir().or_else(|| project_dirs().map(|dirs| dirs.config_dir().to_path_buf()))
+}
+
+/// Emplacement par défaut du coffre: répertoire portable, sinon répertoire de données de l'OS
+pub fn default_vault_path() -> Option<PathBuf> {
+    portable_dir()
+        .or_else(|| project_dirs().map(|dirs| dirs.data_dir().to_path_buf()))
+        .map(|dir| dir.join(DEFAULT_VAULT_FILE))
+}
+
+impl Settings {
+    pub fn format_timestamp(&self, dt: &DateTime<Utc>) -> String {
+        match self.time_display {
+            TimeDisplay::Local => format_timestamp_in(dt, &Local, &self.date_format),
+            TimeDisplay::Utc => format_timestamp_in(dt, &Utc, &self.date_format),
+        }
+    }
+
+    /// Charge les préférences; retombe sur les valeurs par défaut si absentes ou illisibles
+    pub fn load() -> Self {
+        config_dir()
+            .map(|dir| dir.join(SETTINGS_FILE))
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let dir = config_dir().ok_or("Répertoire de configuration introuvable")?;
+        fs::create_dir_all(&dir)?;
+
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(dir.join(SETTINGS_FILE), json)?;
+
+        Ok(())
+    }
+
+    /// Exporte la configuration (préférences et profils du générateur, aucun secret)
+    pub fn export_config(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Importe une configuration en la fusionnant avec la configuration courante
+    pub fn import_config(
+        &mut self,
+        path: &Path,
+        include_security: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let contents = fs::read_to_string(path)?;
+        let imported: Settings = serde_json::from_str(&contents)?;
+        self.merge(imported, include_security);
+        Ok(())
+    }
+
+    /// Les profils et les modèles de copie sont fusionnés par nom (l'import l'emporte); les
+    /// préférences simples sont reprises. Le raccourci de verrouillage et les liens `mdp://`
+    /// ne sont repris que sur demande explicite (`include_security`).
+    pub fn merge(&mut self, imported: Settings, include_security: bool) {
+        for (name, options) in imported.generator_presets {
+            match self.generator_presets.iter_mut().find(|(n, _)| *n == name) {
+                Some(existing) => existing.1 = options,
+                None => self.generator_presets.push((name, options)),
+            }
+        }
+
+        for template in imported.copy_templates {
+            match self.copy_templates.iter_mut().find(|t| t.name == template.name) {
+                Some(existing) => *existing = template,
+                None => self.copy_templates.push(template),
+            }
+        }
+
+        self.clipboard_warning_dismissed |= imported.clipboard_warning_dismissed;
+        self.time_display = imported.time_display;
+        self.date_format = imported.date_format;
+        self.age_warning_days = imported.age_warning_days;
+        self.age_critical_days = imported.age_critical_days;
+        self.generator_options = imported.generator_options;
+        self.reveal_all_seconds = imported.reveal_all_seconds;
+        self.notify_clipboard_cleared = imported.notify_clipboard_cleared;
+
+        if include_security {
+            self.lock_hotkey = imported.lock_hotkey;
+            self.deep_links_enabled = imported.deep_links_enabled;
+        }
+    }
+}
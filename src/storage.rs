@@ -1,103 +1,1342 @@
 use crate::crypto::*;
-use crate::models::{Entry, Vault, VaultFile};
+use crate::models::{
+    BreachStatus, Entry, EntryKind, ManifestSignature, SealedNotes, Vault, VaultBlob, VaultFile, VaultManifest,
+    VaultMeta, VAULT_FILE_VERSION,
+};
+use crate::totp::parse_totp;
+use crate::vault_storage::{FileStorage, VaultStorage};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt;
 use std::fs;
-use std::path::Path;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use unicode_normalization::is_nfc;
+use zeroize::Zeroize;
 
-pub fn save_vault(
-    vault: &Vault,
-    path: &Path,
-    master_password: &str,
-) -> Result<(), Box<dyn std::error::Error>> {
-    // Sérialiser le coffre
-    let plaintext = serde_json::to_string(vault)?;
+/// Erreurs du stockage, assez précises pour que l'interface et la CLI les distinguent
+#[derive(Debug)]
+pub enum StorageError {
+    Io(io::Error),
+    /// JSON illisible (fichier de coffre, manifeste, coffre déchiffré)
+    Serialization(serde_json::Error),
+    Csv(csv::Error),
+    /// Chiffrement, dérivation, ou mot de passe incorrect (`CryptoError::DecryptionFailed`)
+    Crypto(CryptoError),
+    /// Contenu valide mais inattendu: version, encodage, signature, champ manquant…
+    Format(String),
+    /// Le chemin ne désigne pas un fichier utilisable (dossier, boucle de liens, périphérique…)
+    InvalidPath(PathBuf, String),
+    /// Copie d'accès d'urgence ouverte avant sa date de disponibilité
+    NotYetAvailable(DateTime<Utc>),
+}
+
+impl StorageError {
+    /// Aucun emplacement ne s'ouvre avec ce mot de passe
+    pub fn is_wrong_password(&self) -> bool {
+        matches!(self, StorageError::Crypto(CryptoError::DecryptionFailed))
+    }
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Io(e) => write!(f, "Erreur d'accès au fichier: {}", e),
+            StorageError::Serialization(e) => write!(f, "Contenu illisible: {}", e),
+            StorageError::Csv(e) => write!(f, "CSV invalide: {}", e),
+            StorageError::Crypto(e) => write!(f, "{}", e),
+            StorageError::Format(msg) => write!(f, "{}", msg),
+            StorageError::InvalidPath(path, reason) => {
+                write!(f, "Chemin de coffre invalide ({}): {}", path.display(), reason)
+            }
+            StorageError::NotYetAvailable(not_before) => write!(
+                f,
+                "Copie d'accès d'urgence verrouillée jusqu'au {}",
+                not_before.format("%d/%m/%Y %H:%M UTC")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StorageError::Io(e) => Some(e),
+            StorageError::Serialization(e) => Some(e),
+            StorageError::Csv(e) => Some(e),
+            StorageError::Crypto(e) => Some(e),
+            StorageError::Format(_)
+            | StorageError::InvalidPath(..)
+            | StorageError::NotYetAvailable(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for StorageError {
+    fn from(e: io::Error) -> Self {
+        // Un support renvoie ses erreurs de chemin sous forme d'io::Error: les récupérer telles quelles
+        if !e.get_ref().map_or(false, |inner| inner.is::<StorageError>()) {
+            return StorageError::Io(e);
+        }
+        *e.into_inner()
+            .and_then(|inner| inner.downcast().ok())
+            .expect("erreur interne vérifiée par get_ref")
+    }
+}
+
+impl From<StorageError> for io::Error {
+    fn from(e: StorageError) -> Self {
+        match e {
+            StorageError::Io(e) => e,
+            other => io::Error::new(io::ErrorKind::InvalidInput, other),
+        }
+    }
+}
+
+impl From<serde_json::Error> for StorageError {
+    fn from(e: serde_json::Error) -> Self {
+        StorageError::Serialization(e)
+    }
+}
+
+impl From<csv::Error> for StorageError {
+    fn from(e: csv::Error) -> Self {
+        StorageError::Csv(e)
+    }
+}
+
+impl From<CryptoError> for StorageError {
+    fn from(e: CryptoError) -> Self {
+        StorageError::Crypto(e)
+    }
+}
+
+impl From<std::string::FromUtf8Error> for StorageError {
+    fn from(e: std::string::FromUtf8Error) -> Self {
+        StorageError::Format(format!("Texte déchiffré invalide: {}", e))
+    }
+}
+
+impl From<ed25519_dalek::SignatureError> for StorageError {
+    fn from(e: ed25519_dalek::SignatureError) -> Self {
+        StorageError::Format(format!("Signature du manifeste invalide: {}", e))
+    }
+}
+
+#[cfg(feature = "age")]
+impl From<age::DecryptError> for StorageError {
+    fn from(e: age::DecryptError) -> Self {
+        StorageError::Format(format!("Fichier age illisible: {}", e))
+    }
+}
+
+impl From<String> for StorageError {
+    fn from(msg: String) -> Self {
+        StorageError::Format(msg)
+    }
+}
+
+impl From<&str> for StorageError {
+    fn from(msg: &str) -> Self {
+        StorageError::Format(msg.to_string())
+    }
+}
+
+/// Emplacement d'un coffre dans le fichier: le principal ou le second (leurre)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VaultSlot {
+    Primary,
+    Secondary,
+}
+
+/// Coffre déchiffré accompagné des informations sur le fichier d'origine
+pub struct LoadedVault {
+    pub vault: Vault,
+    pub slot: VaultSlot,
+    /// Version d'origine si le fichier a été migré au chargement
+    pub migrated_from: Option<u32>,
+    pub session: VaultSession,
+    /// Temps d'une dérivation de clé (celle de l'emplacement principal), absent si la clé a été fournie directement
+    pub derive_time: Option<Duration>,
+}
+
+/// Clé dérivée conservée tant que le coffre est ouvert, pour sauvegarder sans le mot de passe
+pub struct VaultSession {
+    key: SecureKey,
+    salt: Vec<u8>,
+    params: CryptoParams,
+    slot: VaultSlot,
+}
+
+impl VaultSession {
+    pub fn slot(&self) -> VaultSlot {
+        self.slot
+    }
+
+    /// Parts de Shamir de la clé de session; invalidées par un ré-chiffrement
+    pub fn key_shares(&self, n: u8, k: u8) -> Vec<String> {
+        split_key(self.key.as_bytes(), n, k)
+    }
+}
 
-    // Générer salt et nonce
+fn seal(
+    plaintext: &[u8],
+    master_password: &str,
+    params: &CryptoParams,
+) -> Result<(VaultBlob, SecureKey, Vec<u8>), StorageError> {
+    // Générer le salt
     let salt = generate_salt();
-    let nonce = generate_nonce();
 
     // Dériver la clé
-    let params = CryptoParams::default();
-    let key = derive_key(master_password, &salt, &params)?;
+    let key = derive_key(master_password, &salt, params)?;
     let secure_key = SecureKey::new(key);
 
+    let blob = seal_with_key(plaintext, &secure_key, &salt)?;
+
+    Ok((blob, secure_key, salt))
+}
+
+/// Chiffre avec une clé déjà dérivée et un nonce neuf
+fn seal_with_key(
+    plaintext: &[u8],
+    key: &SecureKey,
+    salt: &[u8],
+) -> Result<VaultBlob, StorageError> {
+    let nonce = generate_nonce();
+
     // Chiffrer
-    let ciphertext = encrypt(plaintext.as_bytes(), secure_key.as_bytes(), &nonce)?;
+    let ciphertext = encrypt(plaintext, key.as_bytes(), &nonce)?;
+
+    Ok(VaultBlob {
+        salt: encode_base64(salt),
+        nonce: encode_base64(&nonce),
+        ciphertext: encode_base64(&ciphertext),
+    })
+}
+
+fn unseal(
+    blob: &VaultBlob,
+    master_password: &str,
+    params: &CryptoParams,
+) -> Result<(SecureBuffer, SecureKey), StorageError> {
+    unseal_with(blob, |salt| derive_key(master_password, salt, params))
+}
+
+/// Ouvre un emplacement du coffre. Un coffre créé avant la normalisation NFC avec une
+/// phrase décomposée (macOS) a été dérivé des octets saisis: on les réessaie alors, et
+/// `true` indique que l'emplacement doit être ré-enregistré sous la forme NFC.
+fn unseal_slot(
+    blob: &VaultBlob,
+    master_password: &str,
+    params: &CryptoParams,
+) -> Result<(SecureBuffer, SecureKey, bool), StorageError> {
+    match unseal(blob, master_password, params) {
+        Ok((plaintext, key)) => Ok((plaintext, key, false)),
+        Err(e) if e.is_wrong_password() && !is_nfc(master_password) => {
+            unseal_with(blob, |salt| derive_key_unnormalized(master_password, salt, params))
+                .map(|(plaintext, key)| (plaintext, key, true))
+                .map_err(|_| e)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+fn unseal_with(
+    blob: &VaultBlob,
+    derive: impl FnOnce(&[u8]) -> Result<Vec<u8>, CryptoError>,
+) -> Result<(SecureBuffer, SecureKey), StorageError> {
+    // Décoder base64
+    let salt = decode_base64(&blob.salt)?;
+    let nonce = decode_base64(&blob.nonce)?;
+    let ciphertext = decode_base64(&blob.ciphertext)?;
+
+    // Dériver la clé
+    let key = derive(&salt)?;
+    let secure_key = SecureKey::new(key);
+
+    // Déchiffrer
+    let plaintext = SecureBuffer::new(decrypt(&ciphertext, secure_key.as_bytes(), &nonce)?);
+
+    Ok((plaintext, secure_key))
+}
+
+/// Bourrage aléatoire de même taille qu'un chiffré réel
+fn filler(ciphertext_len: usize) -> VaultBlob {
+    VaultBlob {
+        salt: encode_base64(&generate_salt()),
+        nonce: encode_base64(&generate_nonce()),
+        ciphertext: encode_base64(&random_bytes(ciphertext_len)),
+    }
+}
+
+/// Lit et migre le fichier; renvoie aussi la version d'origine s'il a été migré
+fn read_vault(storage: &dyn VaultStorage) -> Result<(VaultFile, Option<u32>), StorageError> {
+    let mut vault_file: VaultFile = serde_json::from_slice(&storage.read_bytes()?)?;
+    let migrated_from = migrate(&mut vault_file)?;
+    Ok((vault_file, migrated_from))
+}
+
+/// Comme `read_vault`, `None` si le coffre n'existe pas encore
+fn read_existing(storage: &dyn VaultStorage) -> Result<Option<VaultFile>, StorageError> {
+    match read_vault(storage) {
+        Ok((vault_file, _)) => Ok(Some(vault_file)),
+        Err(StorageError::Io(e)) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Met à niveau un fichier lu vers le format courant; renvoie la version d'origine si migrée
+fn migrate(vault_file: &mut VaultFile) -> Result<Option<u32>, StorageError> {
+    let from = vault_file.version;
+
+    if from > VAULT_FILE_VERSION {
+        return Err(format!("Format de coffre v{} non supporté", from).into());
+    }
+
+    if from == VAULT_FILE_VERSION {
+        return Ok(None);
+    }
+
+    // v1 → v2: les paramètres KDF absents valent les défauts, déjà remplis par serde
+    vault_file.version = VAULT_FILE_VERSION;
+
+    Ok(Some(from))
+}
 
+fn write_vault(
+    storage: &dyn VaultStorage,
+    params: CryptoParams,
+    primary: VaultBlob,
+    secondary: VaultBlob,
+    metadata: Option<VaultMeta>,
+) -> Result<(), StorageError> {
     // Créer la structure du fichier
     let vault_file = VaultFile {
-        version: 1,
+        version: VAULT_FILE_VERSION,
         kdf: "argon2id".to_string(),
-        salt: encode_base64(&salt),
-        nonce: encode_base64(&nonce),
-        ciphertext: encode_base64(&ciphertext),
+        kdf_params: params,
+        salt: primary.salt,
+        nonce: primary.nonce,
+        ciphertext: primary.ciphertext,
+        secondary: Some(secondary),
+        metadata,
     };
 
     // Sauvegarder
     let json = serde_json::to_string_pretty(&vault_file)?;
-    fs::write(path, json)?;
+    storage.write_bytes(json.as_bytes())?;
 
     Ok(())
 }
 
+/// Crée un nouveau fichier de coffre; un éventuel fichier existant est remplacé
+pub fn create_vault_file(
+    vault: &Vault,
+    path: &Path,
+    master_password: &str,
+) -> Result<VaultSession, StorageError> {
+    create_vault_file_with_params(vault, path, master_password, CryptoParams::default())
+}
+
+/// Crée le fichier avec des paramètres KDF choisis, enregistrés dans le fichier
+pub fn create_vault_file_with_params(
+    vault: &Vault,
+    path: &Path,
+    master_password: &str,
+    params: CryptoParams,
+) -> Result<VaultSession, StorageError> {
+    create_vault_in(vault, &FileStorage::new(path), master_password, params)
+}
+
+/// Crée un coffre sur un support quelconque
+pub fn create_vault_in(
+    vault: &Vault,
+    storage: &dyn VaultStorage,
+    master_password: &str,
+    params: CryptoParams,
+) -> Result<VaultSession, StorageError> {
+    let plaintext = serde_json::to_string(vault)?;
+    let (primary, key, salt) = seal(plaintext.as_bytes(), master_password, &params)?;
+    let secondary = filler(decode_base64(&primary.ciphertext)?.len());
+    let metadata = public_metadata(vault);
+
+    write_vault(storage, params.clone(), primary, secondary, metadata)?;
+
+    Ok(VaultSession {
+        key,
+        salt,
+        params,
+        slot: VaultSlot::Primary,
+    })
+}
+
+/// Métadonnées en clair à écrire: celles du coffre enregistré s'il les publie, quel que soit
+/// son emplacement. Un leurre ne recopie jamais celles du principal: leur nombre d'entrées,
+/// affiché à côté du leurre, trahirait le coffre caché.
+fn public_metadata(vault: &Vault) -> Option<VaultMeta> {
+    vault.settings.expose_metadata.then(|| VaultMeta::of(vault))
+}
+
+/// Lit l'aperçu en clair d'un coffre, sans mot de passe
+pub fn load_vault_metadata(path: &Path) -> Result<VaultMeta, StorageError> {
+    let (vault_file, _) = read_vault(&FileStorage::new(path))?;
+    vault_file
+        .metadata
+        .ok_or_else(|| "Ce coffre ne publie pas de métadonnées".into())
+}
+
+/// Place un chiffré dans son emplacement en conservant l'autre tel quel
+fn place_in_slot(
+    sealed: VaultBlob,
+    slot: VaultSlot,
+    existing: Option<VaultFile>,
+) -> Result<(VaultBlob, VaultBlob), StorageError> {
+    let sealed_len = decode_base64(&sealed.ciphertext)?.len();
+
+    match (slot, existing) {
+        (VaultSlot::Primary, Some(file)) => {
+            let secondary = file.secondary.unwrap_or_else(|| filler(sealed_len));
+            Ok((sealed, secondary))
+        }
+        (VaultSlot::Primary, None) => Ok((sealed, filler(sealed_len))),
+        (VaultSlot::Secondary, Some(file)) => Ok((file.primary(), sealed)),
+        (VaultSlot::Secondary, None) => {
+            Err("Le coffre principal doit exister avant le coffre leurre".into())
+        }
+    }
+}
+
+/// Sauvegarde avec la clé de session (même salt, nonce neuf), sans nouvelle dérivation
+pub fn save_vault_session(
+    vault: &Vault,
+    path: &Path,
+    session: &VaultSession,
+) -> Result<(), StorageError> {
+    save_vault_session_to(vault, &FileStorage::new(path), session)
+}
+
+pub fn save_vault_session_to(
+    vault: &Vault,
+    storage: &dyn VaultStorage,
+    session: &VaultSession,
+) -> Result<(), StorageError> {
+    let existing = read_existing(storage)?;
+
+    let plaintext = serde_json::to_string(vault)?;
+    let sealed = seal_with_key(plaintext.as_bytes(), &session.key, &session.salt)?;
+    let metadata = public_metadata(vault);
+    let (primary, secondary) = place_in_slot(sealed, session.slot, existing)?;
+
+    write_vault(storage, session.params.clone(), primary, secondary, metadata)
+}
+
+/// Coffre non enregistré, chiffré avec la clé de session pour survivre au verrouillage
+pub struct SealedSnapshot {
+    blob: VaultBlob,
+}
+
+/// Chiffre le coffre ouvert avec la clé de session, sans écrire sur disque
+pub fn seal_snapshot(vault: &Vault, session: &VaultSession) -> Result<SealedSnapshot, StorageError> {
+    let mut plaintext = serde_json::to_string(vault)?;
+    let blob = seal_with_key(plaintext.as_bytes(), &session.key, &session.salt);
+    plaintext.zeroize();
+    Ok(SealedSnapshot { blob: blob? })
+}
+
+/// Rouvre un instantané avec la session d'un nouveau déverrouillage: même fichier et même
+/// emplacement redonnent la même clé, sinon l'instantané reste illisible
+pub fn open_snapshot(snapshot: &SealedSnapshot, session: &VaultSession) -> Result<Vault, StorageError> {
+    let nonce = decode_base64(&snapshot.blob.nonce)?;
+    let ciphertext = decode_base64(&snapshot.blob.ciphertext)?;
+    let plaintext = SecureBuffer::new(decrypt(&ciphertext, session.key.as_bytes(), &nonce)?);
+    Ok(serde_json::from_slice(plaintext.as_bytes())?)
+}
+
+pub fn save_vault(
+    vault: &Vault,
+    path: &Path,
+    master_password: &str,
+) -> Result<(), StorageError> {
+    save_vault_to(vault, &FileStorage::new(path), master_password)
+}
+
+pub fn save_vault_to(
+    vault: &Vault,
+    storage: &dyn VaultStorage,
+    master_password: &str,
+) -> Result<(), StorageError> {
+    save_vault_slot_to(vault, storage, master_password, VaultSlot::Primary)
+}
+
+/// Sauvegarde un coffre dans l'emplacement donné en conservant l'autre intact
+pub fn save_vault_slot(
+    vault: &Vault,
+    path: &Path,
+    master_password: &str,
+    slot: VaultSlot,
+) -> Result<(), StorageError> {
+    save_vault_slot_to(vault, &FileStorage::new(path), master_password, slot)
+}
+
+pub fn save_vault_slot_to(
+    vault: &Vault,
+    storage: &dyn VaultStorage,
+    master_password: &str,
+    slot: VaultSlot,
+) -> Result<(), StorageError> {
+    let existing = read_existing(storage)?;
+
+    // Les deux emplacements partagent les paramètres KDF du fichier
+    let params = existing
+        .as_ref()
+        .map(|file| file.kdf_params.clone())
+        .unwrap_or_default();
+
+    let plaintext = serde_json::to_string(vault)?;
+    let (sealed, _, _) = seal(plaintext.as_bytes(), master_password, &params)?;
+    let metadata = public_metadata(vault);
+    let (primary, secondary) = place_in_slot(sealed, slot, existing)?;
+
+    write_vault(storage, params, primary, secondary, metadata)
+}
+
+/// Ré-chiffre l'emplacement ouvert avec un nouveau salt, une nouvelle clé et un nouveau nonce,
+/// sans changer le mot de passe. L'autre emplacement est conservé tel quel.
+pub fn reencrypt_vault(
+    vault: &Vault,
+    path: &Path,
+    master_password: &str,
+    slot: VaultSlot,
+) -> Result<VaultSession, StorageError> {
+    let (_, opened_slot) = load_vault_slot(path, master_password)?;
+    if opened_slot != slot {
+        return Err("Mot de passe maître incorrect".into());
+    }
+
+    let storage = FileStorage::new(path);
+    let (existing, _) = read_vault(&storage)?;
+    let params = existing.kdf_params.clone();
+
+    let plaintext = serde_json::to_string(vault)?;
+    let (sealed, key, salt) = seal(plaintext.as_bytes(), master_password, &params)?;
+    let metadata = public_metadata(vault);
+    let (primary, secondary) = place_in_slot(sealed, slot, Some(existing))?;
+
+    write_vault(&storage, params.clone(), primary, secondary, metadata)?;
+
+    Ok(VaultSession {
+        key,
+        salt,
+        params,
+        slot,
+    })
+}
+
 pub fn load_vault(
     path: &Path,
     master_password: &str,
-) -> Result<Vault, Box<dyn std::error::Error>> {
-    // Charger le fichier
-    let contents = fs::read_to_string(path)?;
-    let vault_file: VaultFile = serde_json::from_str(&contents)?;
+) -> Result<Vault, StorageError> {
+    load_vault_from(&FileStorage::new(path), master_password)
+}
 
-    // Décoder base64
-    let salt = decode_base64(&vault_file.salt)?;
-    let nonce = decode_base64(&vault_file.nonce)?;
-    let ciphertext = decode_base64(&vault_file.ciphertext)?;
+pub fn load_vault_from(
+    storage: &dyn VaultStorage,
+    master_password: &str,
+) -> Result<Vault, StorageError> {
+    open_vault_from(storage, master_password, |_| {}).map(|loaded| loaded.vault)
+}
 
-    // Dériver la clé
-    let params = CryptoParams::default();
-    let key = derive_key(master_password, &salt, &params)?;
-    let secure_key = SecureKey::new(key);
+/// Charge le coffre correspondant au mot de passe et indique son emplacement
+pub fn load_vault_slot(
+    path: &Path,
+    master_password: &str,
+) -> Result<(Vault, VaultSlot), StorageError> {
+    open_vault_file(path, master_password).map(|loaded| (loaded.vault, loaded.slot))
+}
 
-    // Déchiffrer
-    let plaintext = decrypt(&ciphertext, secure_key.as_bytes(), &nonce)?;
+/// Étapes de l'ouverture d'un coffre, pour afficher la progression
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoadStage {
+    Reading,
+    DerivingKey,
+    Decoding,
+}
+
+impl LoadStage {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LoadStage::Reading => "Lecture du fichier…",
+            LoadStage::DerivingKey => "Dérivation de la clé…",
+            LoadStage::Decoding => "Lecture des entrées…",
+        }
+    }
+}
+
+/// Charge le coffre en migrant au besoin les anciens formats de fichier
+pub fn open_vault_file(
+    path: &Path,
+    master_password: &str,
+) -> Result<LoadedVault, StorageError> {
+    open_vault_file_with_progress(path, master_password, |_| {})
+}
+
+/// Ouvre un coffre avec une clé déjà dérivée (reconstituée depuis des parts de Shamir)
+pub fn open_vault_file_with_key(path: &Path, key: &[u8]) -> Result<LoadedVault, StorageError> {
+    let (vault_file, migrated_from) = read_vault(&FileStorage::new(path))?;
+
+    let primary = vault_file.primary();
+    let candidates = std::iter::once((&primary, VaultSlot::Primary))
+        .chain(vault_file.secondary.as_ref().map(|blob| (blob, VaultSlot::Secondary)));
+
+    for (blob, slot) in candidates {
+        let nonce = decode_base64(&blob.nonce)?;
+        let ciphertext = decode_base64(&blob.ciphertext)?;
+        let Ok(plaintext) = decrypt(&ciphertext, key, &nonce).map(SecureBuffer::new) else {
+            continue;
+        };
+
+        let vault = serde_json::from_slice(plaintext.as_bytes());
+
+        return Ok(LoadedVault {
+            vault: vault?,
+            slot,
+            migrated_from,
+            session: VaultSession {
+                key: SecureKey::new(key.to_vec()),
+                salt: decode_base64(&blob.salt)?,
+                params: vault_file.kdf_params.clone(),
+                slot,
+            },
+            derive_time: None,
+        });
+    }
+
+    Err(CryptoError::InvalidKey.into())
+}
+
+/// Comme `open_vault_file`, en signalant chaque étape à `progress`
+pub fn open_vault_file_with_progress(
+    path: &Path,
+    master_password: &str,
+    progress: impl FnMut(LoadStage),
+) -> Result<LoadedVault, StorageError> {
+    open_vault_from(&FileStorage::new(path), master_password, progress)
+}
+
+/// Ouvre un coffre depuis un support quelconque, en signalant chaque étape à `progress`
+pub fn open_vault_from(
+    storage: &dyn VaultStorage,
+    master_password: &str,
+    mut progress: impl FnMut(LoadStage),
+) -> Result<LoadedVault, StorageError> {
+    progress(LoadStage::Reading);
+    let (vault_file, migrated_from) = read_vault(storage)?;
+    let params = &vault_file.kdf_params;
+
+    progress(LoadStage::DerivingKey);
+    let derive_start = Instant::now();
+    let primary = vault_file.primary();
+    // Les deux emplacements sont toujours dérivés: la durée ne trahit pas celui qui s'ouvre
+    let opened_primary = unseal_slot(&primary, master_password, params);
+    // Durée d'une seule dérivation, celle du principal: identique quel que soit l'emplacement ouvert
+    let derive_time = derive_start.elapsed();
+    let opened_secondary = vault_file
+        .secondary
+        .as_ref()
+        .map(|blob| (blob, unseal_slot(blob, master_password, params)));
+    let (blob, (plaintext, key, legacy), slot) = match (opened_primary, opened_secondary) {
+        (Ok(opened), _) => (&primary, opened, VaultSlot::Primary),
+        (Err(e), Some((secondary, opened))) if e.is_wrong_password() => {
+            (secondary, opened?, VaultSlot::Secondary)
+        }
+        (Err(e), _) => return Err(e),
+    };
 
     // Désérialiser
+    progress(LoadStage::Decoding);
+    let vault: Vault = serde_json::from_slice(plaintext.as_bytes())?;
+
+    let resealed = legacy.then(|| reseal_normalized(storage, &vault_file, &plaintext, master_password, slot));
+    let (key, salt) = match resealed {
+        Some(Ok(resealed)) => resealed,
+        // Ré-enregistrement impossible (support en lecture seule…): l'ancienne clé reste valable
+        _ => (key, decode_base64(&blob.salt)?),
+    };
+
+    let session = VaultSession {
+        key,
+        salt,
+        params: params.clone(),
+        slot,
+    };
+
+    Ok(LoadedVault {
+        vault,
+        slot,
+        migrated_from,
+        session,
+        derive_time: Some(derive_time),
+    })
+}
+
+/// Ré-écrit un emplacement ouvert par l'ancienne dérivation sous la clé normalisée NFC
+fn reseal_normalized(
+    storage: &dyn VaultStorage,
+    vault_file: &VaultFile,
+    plaintext: &SecureBuffer,
+    master_password: &str,
+    slot: VaultSlot,
+) -> Result<(SecureKey, Vec<u8>), StorageError> {
+    let params = vault_file.kdf_params.clone();
+    let (sealed, key, salt) = seal(plaintext.as_bytes(), master_password, &params)?;
+    let (primary, secondary) = place_in_slot(sealed, slot, Some(vault_file.clone()))?;
+    write_vault(storage, params, primary, secondary, vault_file.metadata.clone())?;
+    Ok((key, salt))
+}
+
+/// Vérifie le mot de passe maître sans désérialiser le coffre
+pub fn verify_master_password(
+    path: &Path,
+    master_password: &str,
+) -> Result<bool, StorageError> {
+    let (vault_file, _) = read_vault(&FileStorage::new(path))?;
+    let mut blobs = vec![vault_file.primary()];
+    blobs.extend(vault_file.secondary);
+
+    // Le tag GCM ne peut être validé qu'en déchiffrant; le clair est jeté aussitôt.
+    // Chaque emplacement est essayé, même après un succès, pour une durée constante.
+    let results: Vec<_> = blobs
+        .iter()
+        .map(|blob| unseal_slot(blob, master_password, &vault_file.kdf_params))
+        .collect();
+
+    let mut found = false;
+    for result in results {
+        match result {
+            // Le tampon est effacé à sa libération
+            Ok(_) => found = true,
+            Err(e) if e.is_wrong_password() => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(found)
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Écrit le manifeste détaché du fichier chiffré `vault_path`
+pub fn write_manifest(
+    vault: &Vault,
+    vault_path: &Path,
+    out_path: &Path,
+) -> Result<(), StorageError> {
+    write_manifest_signed(vault, vault_path, out_path, None)
+}
+
+/// Comme `write_manifest`, en signant le manifeste si une clé Ed25519 est fournie
+pub fn write_manifest_signed(
+    vault: &Vault,
+    vault_path: &Path,
+    out_path: &Path,
+    signing_key: Option<&SigningKey>,
+) -> Result<(), StorageError> {
+    let contents = fs::read(vault_path)?;
+
+    let mut manifest = VaultManifest {
+        file_name: vault_path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        file_size: contents.len() as u64,
+        sha256: sha256_hex(&contents),
+        entry_count: vault.entries.len(),
+        created_at: Utc::now(),
+        signature: None,
+    };
+
+    if let Some(key) = signing_key {
+        let signature = key.sign(&manifest.signed_payload());
+        manifest.signature = Some(ManifestSignature {
+            public_key: encode_base64(key.verifying_key().as_bytes()),
+            signature: encode_base64(&signature.to_bytes()),
+        });
+    }
+
+    fs::write(out_path, serde_json::to_string_pretty(&manifest)?)?;
+    Ok(())
+}
+
+fn check_signature(manifest: &VaultManifest, signature: &ManifestSignature) -> Result<bool, StorageError> {
+    let public_key: [u8; 32] = decode_base64(&signature.public_key)?
+        .try_into()
+        .map_err(|_| "Clé publique du manifeste invalide")?;
+    let verifying_key = VerifyingKey::from_bytes(&public_key)?;
+    let signature = Signature::from_slice(&decode_base64(&signature.signature)?)?;
+
+    Ok(verifying_key.verify(&manifest.signed_payload(), &signature).is_ok())
+}
+
+/// Vérifie qu'un fichier chiffré correspond à son manifeste (taille, SHA-256 et,
+/// si le manifeste est signé, signature valide pour la clé qu'il contient)
+pub fn verify_manifest(vault_path: &Path, manifest_path: &Path) -> Result<bool, StorageError> {
+    let manifest: VaultManifest = serde_json::from_str(&fs::read_to_string(manifest_path)?)?;
+    let contents = fs::read(vault_path)?;
+
+    if contents.len() as u64 != manifest.file_size || sha256_hex(&contents) != manifest.sha256 {
+        return Ok(false);
+    }
+
+    match &manifest.signature {
+        Some(signature) => check_signature(&manifest, signature),
+        None => Ok(true),
+    }
+}
+
+/// Comme `verify_manifest`, mais exige une signature de la clé de confiance:
+/// un manifeste remplacé et re-signé par un tiers est rejeté
+pub fn verify_manifest_signed(
+    vault_path: &Path,
+    manifest_path: &Path,
+    trusted_key: &VerifyingKey,
+) -> Result<bool, StorageError> {
+    let manifest: VaultManifest = serde_json::from_str(&fs::read_to_string(manifest_path)?)?;
+    let Some(signature) = &manifest.signature else {
+        return Ok(false);
+    };
+
+    if decode_base64(&signature.public_key)? != trusted_key.as_bytes() {
+        return Ok(false);
+    }
+
+    verify_manifest(vault_path, manifest_path)
+}
+
+/// Exporte une sauvegarde chiffrée (`.mdpx`) avec son propre mot de passe
+pub fn export_encrypted(
+    vault: &Vault,
+    path: &Path,
+    backup_password: &str,
+) -> Result<(), StorageError> {
+    create_vault_file(vault, path, backup_password).map(|_| ())
+}
+
+/// Rouvre une sauvegarde avec son mot de passe et vérifie qu'elle contient `expected_entries`
+/// entrées; le contenu déchiffré n'est pas conservé. Renvoie le nombre d'entrées lues.
+pub fn verify_backup(
+    path: &Path,
+    backup_password: &str,
+    expected_entries: usize,
+) -> Result<usize, StorageError> {
+    let count = load_vault(path, backup_password)?.entries.len();
+    if count != expected_entries {
+        return Err(format!(
+            "La sauvegarde contient {} entrée(s) au lieu de {}",
+            count, expected_entries
+        )
+        .into());
+    }
+    Ok(count)
+}
+
+/// Copie le fichier chiffré à côté de lui (`<nom>.<date>.bak`) avant une opération destructive
+pub fn backup_vault_file(path: &Path) -> Result<PathBuf, StorageError> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| StorageError::InvalidPath(path.to_path_buf(), "nom de fichier absent".to_string()))?;
+    let backup = path.with_file_name(format!(
+        "{}.{}.bak",
+        file_name.to_string_lossy(),
+        Utc::now().format("%Y%m%d-%H%M%S")
+    ));
+    fs::copy(path, &backup)?;
+    Ok(backup)
+}
+
+/// Déchiffre une sauvegarde `.mdpx` avec le mot de passe de la sauvegarde,
+/// indépendant de celui du coffre ouvert
+pub fn import_encrypted(
+    path: &Path,
+    backup_password: &str,
+) -> Result<Vec<Entry>, StorageError> {
+    Ok(load_vault(path, backup_password)?.entries)
+}
+
+/// Identifiant de format des copies d'accès d'urgence (`.mdpu`)
+const TIMELOCKED_FORMAT: &str = "mdp-timelock-v1";
+
+#[derive(Serialize, Deserialize)]
+struct TimelockedExport {
+    format: String,
+    not_before: DateTime<Utc>,
+    kdf_params: CryptoParams,
+    #[serde(flatten)]
+    blob: VaultBlob,
+}
+
+/// La date entre dans la dérivation de clé: la modifier dans le fichier rend la copie indéchiffrable
+fn timelock_secret(trustee_password: &str, not_before: &DateTime<Utc>) -> String {
+    format!("{}\n{}", trustee_password, not_before.to_rfc3339())
+}
+
+/// Exporte une copie d'accès d'urgence: chiffrée avec le mot de passe confié à un tiers
+/// de confiance, et refusée à l'import avant `not_before`. La date n'est qu'une garde
+/// logicielle; la vraie protection reste le mot de passe du tiers.
+pub fn export_timelocked(
+    vault: &Vault,
+    path: &Path,
+    trustee_password: &str,
+    not_before: DateTime<Utc>,
+) -> Result<(), StorageError> {
+    let mut plaintext = serde_json::to_vec(vault)?;
+    let mut secret = timelock_secret(trustee_password, &not_before);
+
+    let kdf_params = CryptoParams::default();
+    let sealed = seal(&plaintext, &secret, &kdf_params);
+    plaintext.zeroize();
+    secret.zeroize();
+    let (blob, _, _) = sealed?;
+
+    let file = TimelockedExport {
+        format: TIMELOCKED_FORMAT.to_string(),
+        not_before,
+        kdf_params,
+        blob,
+    };
+    fs::write(path, serde_json::to_string_pretty(&file)?)?;
+    Ok(())
+}
+
+/// Ouvre une copie d'accès d'urgence; refusée tant que sa date n'est pas atteinte
+pub fn import_timelocked(path: &Path, trustee_password: &str) -> Result<Vault, StorageError> {
+    import_timelocked_at(path, trustee_password, Utc::now())
+}
+
+/// Comme `import_timelocked`, à l'instant `now` donné
+pub fn import_timelocked_at(
+    path: &Path,
+    trustee_password: &str,
+    now: DateTime<Utc>,
+) -> Result<Vault, StorageError> {
+    let file: TimelockedExport = serde_json::from_str(&fs::read_to_string(path)?)?;
+    if file.format != TIMELOCKED_FORMAT {
+        return Err(format!("Format de copie d'urgence inconnu: {}", file.format).into());
+    }
+    if now < file.not_before {
+        return Err(StorageError::NotYetAvailable(file.not_before));
+    }
+
+    let mut secret = timelock_secret(trustee_password, &file.not_before);
+    let opened = unseal(&file.blob, &secret, &file.kdf_params);
+    secret.zeroize();
+    let (plaintext, _) = opened?;
+    Ok(serde_json::from_slice(plaintext.as_bytes())?)
+}
+
+/// Chiffre les notes de l'entrée avec un second mot de passe: même coffre ouvert,
+/// elles restent illisibles sans lui (codes de récupération…)
+pub fn seal_notes(entry: &mut Entry, password: &str) -> Result<(), StorageError> {
+    if entry.sealed_notes.is_some() {
+        return Err("Les notes sont déjà protégées".into());
+    }
+    let Some(notes) = entry.notes.as_mut() else {
+        return Err("Aucune note à protéger".into());
+    };
+
+    let params = CryptoParams::default();
+    let (blob, _key, _salt) = seal(notes.as_bytes(), password, &params)?;
+    notes.zeroize();
+    entry.notes = None;
+    entry.sealed_notes = Some(SealedNotes { kdf_params: params, blob });
+
+    Ok(())
+}
+
+/// Déchiffre les notes protégées sans modifier l'entrée
+pub fn open_sealed_notes(entry: &Entry, password: &str) -> Result<String, StorageError> {
+    let sealed = entry.sealed_notes.as_ref().ok_or("Aucune note protégée")?;
+    match unseal(&sealed.blob, password, &sealed.kdf_params) {
+        Ok((plaintext, _key)) => Ok(String::from_utf8(plaintext.as_bytes().to_vec())?),
+        Err(e) if e.is_wrong_password() => Err("Mot de passe des notes incorrect".into()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Retire la protection: les notes redeviennent de simples notes chiffrées avec le coffre
+pub fn remove_notes_seal(entry: &mut Entry, password: &str) -> Result<(), StorageError> {
+    entry.notes = Some(open_sealed_notes(entry, password)?);
+    entry.sealed_notes = None;
+    Ok(())
+}
+
+/// Exporte le coffre vers un fichier `.age` chiffré pour un ou plusieurs destinataires X25519;
+/// chacun peut le déchiffrer avec sa propre identité
+#[cfg(feature = "age")]
+pub fn export_age(
+    vault: &Vault,
+    path: &Path,
+    recipients: &[&str],
+) -> Result<(), StorageError> {
+    use std::io::Write;
+
+    let recipients = recipients
+        .iter()
+        .enumerate()
+        .map(|(i, r)| {
+            r.trim()
+                .parse::<age::x25519::Recipient>()
+                .map(|r| Box::new(r) as Box<dyn age::Recipient + Send>)
+                .map_err(|e| format!("Destinataire age n°{} invalide ({}): {}", i + 1, r.trim(), e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let encryptor = age::Encryptor::with_recipients(recipients).ok_or("Aucun destinataire age")?;
+
+    let mut plaintext = serde_json::to_vec(vault)?;
+    let mut output = Vec::new();
+    let mut writer = encryptor.wrap_output(&mut output)?;
+    writer.write_all(&plaintext)?;
+    writer.finish()?;
+    plaintext.zeroize();
+
+    fs::write(path, output)?;
+
+    Ok(())
+}
+
+/// Importe un coffre depuis un fichier `.age` à l'aide d'un fichier d'identité
+#[cfg(feature = "age")]
+pub fn import_age(path: &Path, identity_path: &Path) -> Result<Vault, StorageError> {
+    use std::io::Read;
+
+    let identities = age::IdentityFile::from_file(identity_path.to_string_lossy().into_owned())?
+        .into_identities()?;
+
+    let data = fs::read(path)?;
+    let decryptor = match age::Decryptor::new(&data[..])? {
+        age::Decryptor::Recipients(d) => d,
+        age::Decryptor::Passphrase(_) => {
+            return Err("Fichier age protégé par phrase de passe non supporté".into())
+        }
+    };
+
+    let mut reader = decryptor.decrypt(identities.iter().map(|i| i.as_ref() as &dyn age::Identity))?;
+    let mut plaintext = Vec::new();
+    reader.read_to_end(&mut plaintext)?;
+
     let vault: Vault = serde_json::from_slice(&plaintext)?;
+    plaintext.zeroize();
 
     Ok(vault)
 }
 
+/// Colonnes incluses dans un export CSV
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvExportOptions {
+    pub include_id: bool,
+    pub include_name: bool,
+    pub include_login: bool,
+    pub include_password: bool,
+    pub include_url: bool,
+    pub include_notes: bool,
+    pub include_tags: bool,
+    pub include_timestamps: bool,
+    /// Écrit les mots de passe en clair au lieu de "***"
+    pub plaintext: bool,
+}
+
+impl Default for CsvExportOptions {
+    fn default() -> Self {
+        // Les six colonnes historiques
+        Self {
+            include_id: false,
+            include_name: true,
+            include_login: true,
+            include_password: true,
+            include_url: true,
+            include_notes: true,
+            include_tags: true,
+            include_timestamps: false,
+            plaintext: false,
+        }
+    }
+}
+
+impl CsvExportOptions {
+    fn header(&self) -> Vec<&'static str> {
+        let mut header = Vec::new();
+        if self.include_id {
+            header.push("id");
+        }
+        if self.include_name {
+            header.push("name");
+        }
+        if self.include_login {
+            header.push("login");
+        }
+        if self.include_password {
+            header.push("password");
+        }
+        if self.include_url {
+            header.push("url");
+        }
+        if self.include_notes {
+            header.push("notes");
+        }
+        if self.include_tags {
+            header.push("tags");
+        }
+        if self.include_timestamps {
+            header.push("created_at");
+            header.push("modified_at");
+        }
+        header
+    }
+
+    fn row(&self, entry: &Entry) -> Vec<String> {
+        let mut row = Vec::new();
+        if self.include_id {
+            row.push(entry.id.to_string());
+        }
+        if self.include_name {
+            row.push(entry.name.clone());
+        }
+        // Les notes sécurisées n'ont ni identifiant ni mot de passe
+        if self.include_login {
+            row.push(if entry.is_secure_note() { String::new() } else { entry.login.clone() });
+        }
+        if self.include_password {
+            row.push(if entry.is_secure_note() {
+                String::new()
+            } else if self.plaintext {
+                entry.password.clone()
+            } else {
+                "***".to_string()
+            });
+        }
+        if self.include_url {
+            // Plusieurs URL séparées par une espace (jamais présente dans une URL valide)
+            row.push(entry.urls.join(" "));
+        }
+        if self.include_notes {
+            row.push(entry.notes.clone().unwrap_or_default());
+        }
+        if self.include_tags {
+            row.push(entry.tags.join(";"));
+        }
+        if self.include_timestamps {
+            row.push(entry.created_at.to_rfc3339());
+            row.push(entry.modified_at.to_rfc3339());
+        }
+        row
+    }
+}
+
 pub fn export_csv(
     vault: &Vault,
     path: &Path,
-    plaintext: bool,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let mut wtr = csv::Writer::from_path(path)?;
+    options: &CsvExportOptions,
+) -> Result<(), StorageError> {
+    write_csv(fs::File::create(path)?, vault, options)
+}
 
-    wtr.write_record(&["name", "login", "password", "url", "notes", "tags"])?;
+/// Identifiant de format des exports CSV chiffrés (`.csv.enc`)
+const ENCRYPTED_CSV_FORMAT: &str = "mdp-csv-enc-v1";
+
+#[derive(Serialize, Deserialize)]
+struct EncryptedCsv {
+    format: String,
+    kdf_params: CryptoParams,
+    #[serde(flatten)]
+    blob: VaultBlob,
+}
+
+/// Exporte le CSV chiffré avec un mot de passe à usage unique, distinct du mot de passe maître.
+/// Les colonnes sont toujours celles par défaut, les seules que `decrypt_csv_export` sait relire;
+/// le chiffrement protège les mots de passe, écrits en clair dans le fichier chiffré.
+pub fn export_csv_encrypted(vault: &Vault, path: &Path, password: &str) -> Result<(), StorageError> {
+    let options = CsvExportOptions {
+        plaintext: true,
+        ..Default::default()
+    };
+    let mut plaintext = Vec::new();
+    write_csv(&mut plaintext, vault, &options)?;
+
+    let kdf_params = CryptoParams::default();
+    let sealed = seal(&plaintext, password, &kdf_params);
+    plaintext.zeroize();
+    let (blob, _, _) = sealed?;
+
+    let file = EncryptedCsv {
+        format: ENCRYPTED_CSV_FORMAT.to_string(),
+        kdf_params,
+        blob,
+    };
+    fs::write(path, serde_json::to_string_pretty(&file)?)?;
+    Ok(())
+}
+
+/// Déchiffre un export `.csv.enc` et en lit les entrées (colonnes par défaut attendues)
+pub fn decrypt_csv_export(path: &Path, password: &str) -> Result<Vec<Entry>, StorageError> {
+    let file: EncryptedCsv = serde_json::from_str(&fs::read_to_string(path)?)?;
+    if file.format != ENCRYPTED_CSV_FORMAT {
+        return Err(format!("Format d'export chiffré inconnu: {}", file.format).into());
+    }
+
+    let (plaintext, _) = unseal(&file.blob, password, &file.kdf_params)?;
+    parse_csv(plaintext.as_bytes())
+}
+
+fn write_csv(
+    writer: impl io::Write,
+    vault: &Vault,
+    options: &CsvExportOptions,
+) -> Result<(), StorageError> {
+    let mut wtr = csv::Writer::from_writer(writer);
+
+    wtr.write_record(options.header())?;
 
     for entry in &vault.entries {
-        let password_field = if plaintext {
-            entry.password.as_str()
-        } else {
-            "***"
-        };
-        
-        wtr.write_record(&[
-            &entry.name,
-            &entry.login,
-            password_field,
-            entry.url.as_deref().unwrap_or(""),
-            entry.notes.as_deref().unwrap_or(""),
-            &entry.tags.join(";"),
-        ])?;
+        let mut row = options.row(entry);
+        wtr.write_record(&row)?;
+        row.zeroize();
     }
 
     wtr.flush()?;
     Ok(())
 }
 
-pub fn import_csv(path: &Path) -> Result<Vec<Entry>, Box<dyn std::error::Error>> {
-    let mut rdr = csv::Reader::from_path(path)?;
+/// Identifiant de format des exports JSON
+const JSON_EXPORT_FORMAT: &str = "mdp-json-v1";
+
+#[derive(Serialize, Deserialize)]
+struct JsonExport {
+    format: String,
+    exported_at: DateTime<Utc>,
+    /// Faux si mots de passe et secrets TOTP ont été retirés
+    include_secrets: bool,
+    entries: Vec<Entry>,
+}
+
+/// Exporte toutes les entrées avec tous leurs champs (tags, URL, TOTP, liens…), contrairement
+/// au CSV. Sans `include_secrets`, mots de passe et secrets TOTP sont retirés.
+pub fn export_json(vault: &Vault, path: &Path, include_secrets: bool) -> Result<(), StorageError> {
+    let mut entries = vault.entries.clone();
+    if !include_secrets {
+        for entry in &mut entries {
+            entry.password.zeroize();
+            entry.password.clear();
+            entry.totp = None;
+        }
+    }
+
+    let file = JsonExport {
+        format: JSON_EXPORT_FORMAT.to_string(),
+        exported_at: Utc::now(),
+        include_secrets,
+        entries,
+    };
+    let mut json = serde_json::to_string_pretty(&file)?;
+    let written = fs::write(path, &json);
+    json.zeroize();
+    written?;
+    Ok(())
+}
+
+/// Lit les entrées d'un export JSON. Un export sans secrets est refusé: fusionné, il
+/// remplacerait mots de passe et TOTP existants par des valeurs vides.
+pub fn import_json(path: &Path) -> Result<Vec<Entry>, StorageError> {
+    let mut text = fs::read_to_string(path)?;
+    let parsed = serde_json::from_str::<JsonExport>(&text);
+    text.zeroize();
+    let file = parsed?;
+    if file.format != JSON_EXPORT_FORMAT {
+        return Err(format!("Format d'export JSON inconnu: {}", file.format).into());
+    }
+    if !file.include_secrets {
+        return Err("Export JSON sans secrets: réservé à la consultation, il ne peut pas être importé".into());
+    }
+    Ok(file.entries)
+}
+
+/// Vérifie les mots de passe contre une liste locale de fuites au format « Pwned Passwords »
+/// (`SHA1:COMPTE` par ligne), lue en flux: rien ne quitte la machine. Met à jour
+/// `breach_status` de chaque entrée vérifiée et renvoie le nombre d'entrées compromises.
+pub fn check_breaches_in_file(vault: &mut Vault, path: &Path) -> Result<usize, StorageError> {
+    let mut wanted: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, entry) in vault.entries.iter().enumerate() {
+        if entry.is_secure_note() || entry.password.is_empty() {
+            continue;
+        }
+        let hash: String = Sha1::digest(entry.password.as_bytes())
+            .iter()
+            .map(|b| format!("{:02X}", b))
+            .collect();
+        wanted.entry(hash).or_default().push(i);
+    }
+
+    let mut counts: HashMap<&str, u64> = HashMap::new();
+    for line in BufReader::new(fs::File::open(path)?).lines() {
+        let line = line?;
+        let Some((hash, count)) = line.trim().split_once(':') else {
+            continue;
+        };
+        if let Some((known, _)) = wanted.get_key_value(hash.to_uppercase().as_str()) {
+            counts.insert(known.as_str(), count.trim().parse().unwrap_or(1));
+        }
+    }
+
+    let mut breached = 0;
+    for (hash, indices) in &wanted {
+        let count = counts.get(hash.as_str()).copied().unwrap_or(0);
+        for &i in indices {
+            vault.entries[i].breach_status = Some(BreachStatus::new(count));
+            if count > 0 {
+                breached += 1;
+            }
+        }
+    }
+    Ok(breached)
+}
+
+pub fn import_csv(path: &Path) -> Result<Vec<Entry>, StorageError> {
+    let text = decode_csv_bytes(&fs::read(path)?)?;
+    parse_csv(text.as_bytes())
+}
+
+/// Décode un CSV exporté par un tableur: BOM UTF-8/UTF-16 retiré, puis UTF-8,
+/// et à défaut Windows-1252 (Latin-1), l'encodage par défaut d'Excel sous Windows
+pub fn decode_csv_bytes(bytes: &[u8]) -> Result<String, StorageError> {
+    if let Some((encoding, bom_len)) = encoding_rs::Encoding::for_bom(bytes) {
+        let (text, had_errors) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+        if had_errors {
+            return Err(format!("CSV mal encodé: contenu invalide pour l'encodage {} annoncé", encoding.name()).into());
+        }
+        return Ok(text.into_owned());
+    }
+
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return Ok(text.to_string());
+    }
+
+    // encoding_rs associe un caractère à chacun des 256 octets, sans jamais signaler d'erreur:
+    // les octets que Windows-1252 ne définit pas ressortent en caractères de contrôle C1
+    let text = encoding_rs::WINDOWS_1252.decode_without_bom_handling(bytes).0;
+    if text.chars().any(|c| c.is_control() && !matches!(c, '\t' | '\r' | '\n')) {
+        return Err("Encodage du CSV non reconnu (ni UTF-8 ni Latin-1)".into());
+    }
+    Ok(text.into_owned())
+}
+
+/// Analyse un CSV (fichier ou texte en mémoire) au format `name,login,password,url,notes,tags[,totp]`
+pub fn parse_csv(reader: impl Read) -> Result<Vec<Entry>, StorageError> {
+    let mut rdr = csv::Reader::from_reader(reader);
     let mut entries = Vec::new();
 
     for result in rdr.records() {
@@ -111,15 +1350,20 @@ pub fn import_csv(path: &Path) -> Result<Vec<Entry>, Box<dyn std::error::Error>>
         let login = record.get(1).unwrap_or("").to_string();
         let password = record.get(2).unwrap_or("").to_string();
 
-        if name.is_empty() || login.is_empty() {
+        // Sans identifiant ni mot de passe mais avec des notes: une note sécurisée exportée
+        let is_note = login.is_empty() && password.is_empty() && record.get(4).is_some_and(|n| !n.is_empty());
+        if name.is_empty() || (login.is_empty() && !is_note) {
             continue;
         }
 
         let mut entry = Entry::new(name, login, password);
+        if is_note {
+            entry.kind = EntryKind::SecureNote;
+        }
 
         if let Some(url) = record.get(3) {
             if !url.is_empty() {
-                entry.url = Some(url.to_string());
+                entry.urls = url.split_whitespace().map(String::from).collect();
             }
         }
 
@@ -135,6 +1379,14 @@ pub fn import_csv(path: &Path) -> Result<Vec<Entry>, Box<dyn std::error::Error>>
             }
         }
 
+        if let Some(totp) = record.get(6) {
+            if !totp.is_empty() {
+                let config = parse_totp(totp)
+                    .map_err(|e| format!("{}: TOTP invalide: {}", entry.name, e))?;
+                entry.totp = Some(config);
+            }
+        }
+
         entries.push(entry);
     }
 
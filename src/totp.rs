@@ -0,0 +1,196 @@
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use zeroize::Zeroize;
+
+const DEFAULT_DIGITS: u32 = 6;
+const DEFAULT_PERIOD: u64 = 30;
+/// Au-delà de 9 chiffres, 10^digits ne tient plus dans un u32
+const DIGITS_RANGE: std::ops::RangeInclusive<u32> = 6..=8;
+const PERIOD_RANGE: std::ops::RangeInclusive<u64> = 1..=3600;
+const BASE32_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Paramètres TOTP d'une entrée (RFC 6238)
+/// Les valeurs hors limites sont refusées à la lecture (fichier ou import)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawTotpConfig")]
+pub struct TotpConfig {
+    /// Secret en base32 normalisé (majuscules, sans espaces ni bourrage)
+    pub secret: String,
+    pub digits: u32,
+    pub period: u64,
+}
+
+/// Forme sérialisée, validée avant de devenir un `TotpConfig`
+#[derive(Deserialize)]
+struct RawTotpConfig {
+    secret: String,
+    digits: u32,
+    period: u64,
+}
+
+impl TryFrom<RawTotpConfig> for TotpConfig {
+    type Error = String;
+
+    fn try_from(raw: RawTotpConfig) -> Result<Self, Self::Error> {
+        Ok(TotpConfig {
+            secret: raw.secret,
+            digits: check_digits(raw.digits)?,
+            period: check_period(raw.period)?,
+        })
+    }
+}
+
+fn check_digits(digits: u32) -> Result<u32, String> {
+    if DIGITS_RANGE.contains(&digits) {
+        Ok(digits)
+    } else {
+        Err(format!("Nombre de chiffres invalide: {}", digits))
+    }
+}
+
+fn check_period(period: u64) -> Result<u64, String> {
+    if PERIOD_RANGE.contains(&period) {
+        Ok(period)
+    } else {
+        Err(format!("Période invalide: {}", period))
+    }
+}
+
+impl TotpConfig {
+    /// Code valable à l'instant donné (secondes Unix)
+    pub fn code_at(&self, unix_time: i64) -> Option<String> {
+        check_digits(self.digits).ok()?;
+        let period = check_period(self.period).ok()? as i64;
+        let counter = unix_time.div_euclid(period) as u64;
+        self.hotp(counter)
+    }
+
+    /// Secondes restantes avant le changement de code
+    pub fn remaining_secs(&self, unix_time: i64) -> u64 {
+        let Ok(period) = check_period(self.period) else {
+            return 0;
+        };
+        period - unix_time.rem_euclid(period as i64) as u64
+    }
+
+    fn hotp(&self, counter: u64) -> Option<String> {
+        let mut key = decode_base32(&self.secret)?;
+        let mac = Hmac::<Sha1>::new_from_slice(&key).ok();
+        key.zeroize();
+        let mut mac = mac?;
+        mac.update(&counter.to_be_bytes());
+        let hash = mac.finalize().into_bytes();
+
+        // Troncature dynamique (RFC 4226, §5.3)
+        let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+        let binary = u32::from_be_bytes([hash[offset], hash[offset + 1], hash[offset + 2], hash[offset + 3]])
+            & 0x7fff_ffff;
+        let code = binary % 10u32.pow(self.digits);
+
+        Some(format!("{:0width$}", code, width = self.digits as usize))
+    }
+}
+
+/// Accepte un secret base32 nu ou une URI `otpauth://totp/...`
+pub fn parse_totp(input: &str) -> Result<TotpConfig, String> {
+    let input = input.trim();
+
+    if input.to_lowercase().starts_with("otpauth://") {
+        return parse_otpauth_uri(input);
+    }
+
+    Ok(TotpConfig {
+        secret: normalize_secret(input)?,
+        digits: DEFAULT_DIGITS,
+        period: DEFAULT_PERIOD,
+    })
+}
+
+fn parse_otpauth_uri(uri: &str) -> Result<TotpConfig, String> {
+    let rest = &uri["otpauth://".len()..];
+
+    let (kind, rest) = rest
+        .split_once('/')
+        .ok_or_else(|| "URI otpauth sans libellé".to_string())?;
+    if !kind.eq_ignore_ascii_case("totp") {
+        return Err(format!("Type otpauth non supporté: {}", kind));
+    }
+
+    let query = rest
+        .split_once('?')
+        .map(|(_, query)| query)
+        .ok_or_else(|| "URI otpauth sans paramètres".to_string())?;
+
+    let mut secret = None;
+    let mut digits = DEFAULT_DIGITS;
+    let mut period = DEFAULT_PERIOD;
+
+    for pair in query.split('&') {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        match key.to_lowercase().as_str() {
+            "secret" => secret = Some(normalize_secret(value)?),
+            "digits" => {
+                digits = value
+                    .parse()
+                    .map_err(|_| format!("Nombre de chiffres invalide: {}", value))
+                    .and_then(check_digits)?;
+            }
+            "period" => {
+                period = value
+                    .parse()
+                    .map_err(|_| format!("Période invalide: {}", value))
+                    .and_then(check_period)?;
+            }
+            "algorithm" if !value.eq_ignore_ascii_case("SHA1") => {
+                return Err(format!("Algorithme non supporté: {}", value));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(TotpConfig {
+        secret: secret.ok_or_else(|| "URI otpauth sans secret".to_string())?,
+        digits,
+        period,
+    })
+}
+
+fn normalize_secret(secret: &str) -> Result<String, String> {
+    let normalized: String = secret
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect::<String>()
+        .trim_end_matches('=')
+        .to_uppercase();
+
+    if normalized.is_empty() {
+        return Err("Secret TOTP vide".to_string());
+    }
+
+    if decode_base32(&normalized).is_none() {
+        return Err("Secret TOTP invalide (base32 attendu)".to_string());
+    }
+
+    Ok(normalized)
+}
+
+/// Décode du base32 RFC 4648 sans bourrage
+pub fn decode_base32(input: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(input.len() * 5 / 8);
+    let mut buffer: u64 = 0;
+    let mut bits = 0;
+
+    for c in input.bytes() {
+        let value = BASE32_ALPHABET.iter().position(|&a| a == c)? as u64;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    Some(bytes)
+}
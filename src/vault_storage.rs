@@ -0,0 +1,106 @@
+//! Supports de stockage du fichier chiffré. Le chiffrement reste dans `storage`:
+//! un support ne voit passer que des octets déjà chiffrés.
+
+use crate::storage::StorageError;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Nombre maximal de liens symboliques suivis avant d'abandonner
+const MAX_SYMLINK_DEPTH: usize = 32;
+
+/// Suit les liens symboliques de `path` et vérifie que la cible est un fichier ordinaire,
+/// ou n'existe pas encore (elle sera créée). Une boucle de liens est détectée au lieu de bloquer.
+pub fn resolve_vault_path(path: &Path) -> Result<PathBuf, StorageError> {
+    let invalid = |reason: String| StorageError::InvalidPath(path.to_path_buf(), reason);
+    let mut current = path.to_path_buf();
+    let mut seen = HashSet::new();
+
+    loop {
+        let meta = match fs::symlink_metadata(&current) {
+            Ok(meta) => meta,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(current),
+            Err(e) => return Err(invalid(e.to_string())),
+        };
+
+        if meta.file_type().is_symlink() {
+            if !seen.insert(current.clone()) || seen.len() > MAX_SYMLINK_DEPTH {
+                return Err(invalid("boucle de liens symboliques".to_string()));
+            }
+            let target = fs::read_link(&current).map_err(|e| invalid(e.to_string()))?;
+            // Une cible relative part du dossier du lien; une cible absolue remplace tout
+            current = match current.parent() {
+                Some(parent) => parent.join(target),
+                None => target,
+            };
+        } else if meta.is_dir() {
+            return Err(invalid("c'est un dossier, pas un fichier".to_string()));
+        } else if !meta.is_file() {
+            return Err(invalid("ce n'est pas un fichier ordinaire".to_string()));
+        } else {
+            return Ok(current);
+        }
+    }
+}
+
+/// Lecture et écriture du fichier de coffre, quel que soit le support
+/// (disque local aujourd'hui, WebDAV ou S3 plus tard)
+pub trait VaultStorage {
+    /// Contenu complet; `io::ErrorKind::NotFound` si le coffre n'existe pas encore
+    fn read_bytes(&self) -> io::Result<Vec<u8>>;
+
+    /// Remplace le contenu complet
+    fn write_bytes(&self, data: &[u8]) -> io::Result<()>;
+}
+
+/// Fichier sur le disque local
+pub struct FileStorage {
+    path: PathBuf,
+}
+
+impl FileStorage {
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+}
+
+impl VaultStorage for FileStorage {
+    fn read_bytes(&self) -> io::Result<Vec<u8>> {
+        fs::read(resolve_vault_path(&self.path)?)
+    }
+
+    fn write_bytes(&self, data: &[u8]) -> io::Result<()> {
+        fs::write(resolve_vault_path(&self.path)?, data)
+    }
+}
+
+/// Stockage en mémoire, sans accès disque (tests)
+#[derive(Default)]
+pub struct MemoryStorage {
+    data: Mutex<Option<Vec<u8>>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl VaultStorage for MemoryStorage {
+    fn read_bytes(&self) -> io::Result<Vec<u8>> {
+        self.data
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Coffre absent"))
+    }
+
+    fn write_bytes(&self, data: &[u8]) -> io::Result<()> {
+        *self.data.lock().unwrap_or_else(|e| e.into_inner()) = Some(data.to_vec());
+        Ok(())
+    }
+}
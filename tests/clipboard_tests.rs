@@ -0,0 +1,68 @@
+use mdp_manager::clipboard::{ClearOutcome, ClipboardBackend, ClipboardManager};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// Presse-papiers simulé: contenu courant et historique des écritures partagés avec le test
+#[derive(Clone, Default)]
+struct FakeClipboard {
+    content: Rc<RefCell<String>>,
+    writes: Rc<RefCell<Vec<String>>>,
+}
+
+impl ClipboardBackend for FakeClipboard {
+    fn set_text(&mut self, text: &str) -> Result<(), String> {
+        *self.content.borrow_mut() = text.to_string();
+        self.writes.borrow_mut().push(text.to_string());
+        Ok(())
+    }
+
+    fn get_text(&mut self) -> Result<String, String> {
+        Ok(self.content.borrow().clone())
+    }
+}
+
+fn manager_with_fake() -> (ClipboardManager, FakeClipboard) {
+    let fake = FakeClipboard::default();
+    (ClipboardManager::new(Some(Box::new(fake.clone()))), fake)
+}
+
+#[test]
+fn test_copy_then_clear_after_delay() {
+    let (mut clipboard, fake) = manager_with_fake();
+    let start = Instant::now();
+
+    clipboard.copy("hunter2", Duration::from_secs(30), start).unwrap();
+    assert_eq!(*fake.content.borrow(), "hunter2");
+
+    assert_eq!(clipboard.clear_if_due(start + Duration::from_secs(10)), None);
+    assert_eq!(*fake.content.borrow(), "hunter2");
+
+    assert_eq!(clipboard.clear_if_due(start + Duration::from_secs(30)), Some(ClearOutcome::Cleared));
+    assert_eq!(*fake.content.borrow(), "");
+    assert_eq!(*fake.writes.borrow(), vec!["hunter2".to_string(), String::new()]);
+
+    // Rien de plus à effacer
+    assert_eq!(clipboard.clear_if_due(start + Duration::from_secs(60)), None);
+}
+
+#[test]
+fn test_clear_does_not_clobber_newer_content() {
+    let (mut clipboard, fake) = manager_with_fake();
+    let start = Instant::now();
+
+    clipboard.copy("hunter2", Duration::from_secs(30), start).unwrap();
+    // L'utilisateur copie autre chose depuis une autre application
+    *fake.content.borrow_mut() = "liste de courses".to_string();
+
+    assert_eq!(clipboard.clear_if_due(start + Duration::from_secs(31)), Some(ClearOutcome::Skipped));
+    assert_eq!(*fake.content.borrow(), "liste de courses");
+}
+
+#[test]
+fn test_unavailable_clipboard_reports_error() {
+    let mut clipboard = ClipboardManager::new(None);
+
+    assert!(!clipboard.is_available());
+    assert!(clipboard.copy("hunter2", Duration::from_secs(30), Instant::now()).is_err());
+}
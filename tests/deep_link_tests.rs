@@ -0,0 +1,30 @@
+#![cfg(feature = "deep-link")]
+
+use mdp_manager::deep_link::*;
+use uuid::Uuid;
+
+#[test]
+fn test_parse_entry_link() {
+    let id = Uuid::new_v4();
+    assert_eq!(parse(&format!("mdp://entry/{}", id)), Some(DeepLink::Entry(id)));
+    assert_eq!(parse(&format!("mdp://entry/{}/", id)), Some(DeepLink::Entry(id)));
+}
+
+#[test]
+fn test_unknown_or_malformed_links_are_ignored() {
+    let id = Uuid::new_v4();
+    assert_eq!(parse("mdp://entry/pas-un-uuid"), None);
+    assert_eq!(parse("mdp://entry/"), None);
+    assert_eq!(parse(&format!("mdp://entry/{}/extra", id)), None);
+    assert_eq!(parse(&format!("mdp://delete/{}", id)), None);
+    assert_eq!(parse(&format!("https://entry/{}", id)), None);
+    assert_eq!(parse("n'importe quoi"), None);
+}
+
+#[test]
+fn test_from_args_skips_other_arguments() {
+    let id = Uuid::new_v4();
+    let args = vec!["--verbose".to_string(), format!("mdp://entry/{}", id)];
+    assert_eq!(from_args(args), Some(DeepLink::Entry(id)));
+    assert_eq!(from_args(Vec::<String>::new()), None);
+}
@@ -20,6 +20,7 @@ fn test_password_includes_required_chars() {
         include_numbers: true,
         include_symbols: true,
         avoid_ambiguous: false,
+        ..Default::default()
     };
 
     let password = generate_password(&options).unwrap();
@@ -39,6 +40,7 @@ fn test_password_avoids_ambiguous() {
         include_numbers: true,
         include_symbols: false,
         avoid_ambiguous: true,
+        ..Default::default()
     };
 
     let password = generate_password(&options).unwrap();
@@ -58,6 +60,7 @@ fn test_password_only_lowercase() {
         include_numbers: false,
         include_symbols: false,
         avoid_ambiguous: false,
+        ..Default::default()
     };
 
     let password = generate_password(&options).unwrap();
@@ -81,6 +84,7 @@ fn test_empty_charset_error() {
         include_numbers: false,
         include_symbols: false,
         avoid_ambiguous: false,
+        ..Default::default()
     };
 
     let result = generate_password(&options);
@@ -96,4 +100,163 @@ fn test_zero_length_error() {
 
     let result = generate_password(&options);
     assert!(result.is_err());
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_generate_many() {
+    let options = PasswordGeneratorOptions::default();
+
+    let passwords = generate_many(&options, 5).unwrap();
+    assert_eq!(passwords.len(), 5);
+    assert!(passwords.iter().all(|p| p.len() == options.length));
+}
+
+#[test]
+fn test_password_enforces_minimums() {
+    let options = PasswordGeneratorOptions {
+        length: 12,
+        min_digits: 4,
+        min_symbols: 3,
+        ..Default::default()
+    };
+
+    for _ in 0..20 {
+        let password = generate_password(&options).unwrap();
+        assert_eq!(password.chars().count(), 12);
+        assert!(password.chars().filter(|c| c.is_ascii_digit()).count() >= 4);
+        assert!(password.chars().filter(|c| !c.is_alphanumeric()).count() >= 3);
+    }
+}
+
+#[test]
+fn test_password_enforces_exact_counts() {
+    let options = PasswordGeneratorOptions {
+        length: 16,
+        min_digits: 2,
+        max_digits: Some(2),
+        min_symbols: 1,
+        max_symbols: Some(1),
+        ..Default::default()
+    };
+
+    for _ in 0..20 {
+        let password = generate_password(&options).unwrap();
+        assert_eq!(password.chars().filter(|c| c.is_ascii_digit()).count(), 2);
+        assert_eq!(password.chars().filter(|c| !c.is_alphanumeric()).count(), 1);
+    }
+}
+
+#[test]
+fn test_over_constrained_minimums_error() {
+    let options = PasswordGeneratorOptions {
+        length: 4,
+        min_digits: 3,
+        min_symbols: 2,
+        ..Default::default()
+    };
+
+    assert!(generate_password(&options).is_err());
+}
+
+#[test]
+fn test_maximums_below_length_error() {
+    let options = PasswordGeneratorOptions {
+        length: 10,
+        include_uppercase: false,
+        include_lowercase: false,
+        include_symbols: false,
+        max_digits: Some(5),
+        ..Default::default()
+    };
+
+    assert!(generate_password(&options).is_err());
+}
+
+#[test]
+fn test_options_entropy_bits() {
+    let lowercase_only = PasswordGeneratorOptions {
+        length: 16,
+        include_uppercase: false,
+        include_lowercase: true,
+        include_numbers: false,
+        include_symbols: false,
+        avoid_ambiguous: false,
+        ..Default::default()
+    };
+    let expected = 16.0 * 26f64.log2();
+    assert!((options_entropy_bits(&lowercase_only) - expected).abs() < 1e-9);
+
+    let all_classes = PasswordGeneratorOptions {
+        length: 16,
+        avoid_ambiguous: false,
+        ..Default::default()
+    };
+    assert!(options_entropy_bits(&all_classes) > options_entropy_bits(&lowercase_only));
+
+    let without_ambiguous = PasswordGeneratorOptions {
+        avoid_ambiguous: true,
+        ..all_classes.clone()
+    };
+    assert!(options_entropy_bits(&without_ambiguous) < options_entropy_bits(&all_classes));
+
+    let longer = PasswordGeneratorOptions {
+        length: 32,
+        ..all_classes.clone()
+    };
+    assert!((options_entropy_bits(&longer) - 2.0 * options_entropy_bits(&all_classes)).abs() < 1e-9);
+}
+
+#[test]
+fn test_options_entropy_without_charset_is_zero() {
+    let options = PasswordGeneratorOptions {
+        include_uppercase: false,
+        include_lowercase: false,
+        include_numbers: false,
+        include_symbols: false,
+        ..Default::default()
+    };
+    assert_eq!(options_entropy_bits(&options), 0.0);
+    assert_eq!(PasswordStrength::from_entropy_bits(0.0), PasswordStrength::Weak);
+}
+
+#[test]
+fn test_generate_random_username_format() {
+    for _ in 0..20 {
+        let username = generate_username(UsernameStyle::Random);
+        assert_eq!(username.len(), 10);
+        assert!(username.chars().next().unwrap().is_ascii_lowercase());
+        assert!(username.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()));
+    }
+}
+
+#[test]
+fn test_generate_word_username_format() {
+    for _ in 0..20 {
+        let username = generate_username(UsernameStyle::Words);
+        let parts: Vec<&str> = username.split('-').collect();
+        assert_eq!(parts.len(), 3, "{}", username);
+        assert!(parts[0].chars().all(|c| c.is_ascii_lowercase()));
+        assert!(parts[1].chars().all(|c| c.is_ascii_lowercase()));
+        assert_eq!(parts[2].len(), 2);
+        assert!(parts[2].parse::<u32>().is_ok());
+    }
+}
+
+#[test]
+fn test_azerty_preset_only_emits_its_symbols() {
+    let options = PasswordGeneratorOptions {
+        length: 64,
+        include_uppercase: false,
+        include_lowercase: false,
+        include_numbers: false,
+        include_symbols: true,
+        symbol_set: SymbolPreset::Azerty,
+        ..Default::default()
+    };
+
+    let allowed = SymbolPreset::Azerty.symbols();
+    for _ in 0..20 {
+        let password = generate_password(&options).unwrap();
+        assert!(password.chars().all(|c| allowed.contains(c)), "{}", password);
+    }
+}
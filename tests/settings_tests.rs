@@ -0,0 +1,119 @@
+use chrono::{FixedOffset, TimeZone, Utc};
+use mdp_manager::password_generator::PasswordGeneratorOptions;
+use mdp_manager::settings::*;
+use tempfile::tempdir;
+
+#[test]
+fn test_format_timestamp_utc() {
+    let dt = Utc.with_ymd_and_hms(2024, 3, 15, 23, 30, 0).unwrap();
+
+    assert_eq!(format_timestamp_in(&dt, &Utc, DEFAULT_DATE_FORMAT), "2024-03-15 23:30");
+}
+
+#[test]
+fn test_format_timestamp_fixed_offset() {
+    let dt = Utc.with_ymd_and_hms(2024, 3, 15, 23, 30, 0).unwrap();
+    let paris = FixedOffset::east_opt(3600).unwrap();
+
+    assert_eq!(format_timestamp_in(&dt, &paris, DEFAULT_DATE_FORMAT), "2024-03-16 00:30");
+    assert_eq!(format_timestamp_in(&dt, &paris, "%d/%m/%Y %Hh%M"), "16/03/2024 00h30");
+}
+
+#[test]
+fn test_invalid_format_falls_back_to_default() {
+    let dt = Utc.with_ymd_and_hms(2024, 3, 15, 23, 30, 0).unwrap();
+
+    assert_eq!(format_timestamp_in(&dt, &Utc, "%Q"), "2024-03-15 23:30");
+}
+
+#[test]
+fn test_config_export_import_roundtrip_merges_presets() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("config.json");
+
+    let mut source = Settings::default();
+    source.date_format = "%d/%m/%Y".to_string();
+    source.generator_presets.push((
+        "Wifi".to_string(),
+        PasswordGeneratorOptions {
+            length: 32,
+            ..Default::default()
+        },
+    ));
+    source.export_config(&path).unwrap();
+
+    let mut target = Settings::default();
+    target.generator_presets.push(("Local".to_string(), PasswordGeneratorOptions::default()));
+    target.import_config(&path, false).unwrap();
+
+    assert_eq!(target.date_format, "%d/%m/%Y");
+    let names: Vec<&str> = target.generator_presets.iter().map(|(n, _)| n.as_str()).collect();
+    assert!(names.contains(&"Local"));
+    assert!(names.contains(&"Wifi"));
+    assert_eq!(names.iter().filter(|n| **n == "Fort 20").count(), 1);
+}
+
+#[test]
+fn test_generator_options_persist_and_default_when_missing() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("config.json");
+
+    let mut source = Settings::default();
+    source.generator_options.length = 28;
+    source.generator_options.include_symbols = false;
+    source.export_config(&path).unwrap();
+
+    let mut target = Settings::default();
+    target.import_config(&path, false).unwrap();
+    assert_eq!(target.generator_options, source.generator_options);
+
+    // Fichier d'une version antérieure, sans réglages du générateur
+    std::fs::write(&path, "{}").unwrap();
+    let mut legacy = Settings::default();
+    legacy.import_config(&path, false).unwrap();
+    assert_eq!(legacy.generator_options, PasswordGeneratorOptions::default());
+}
+
+#[test]
+fn test_portable_dir_requires_marker() {
+    let dir = tempdir().unwrap();
+    assert_eq!(portable_dir_in(dir.path()), None);
+
+    std::fs::write(dir.path().join("portable.txt"), "").unwrap();
+    assert_eq!(portable_dir_in(dir.path()), Some(dir.path().join("mdp_data")));
+}
+
+#[test]
+fn test_onboarding_only_for_new_installations() {
+    assert!(!Settings::default().onboarding_completed);
+
+    // Un fichier écrit avant l'assistant ne le déclenche pas
+    let existing: Settings = serde_json::from_str("{}").unwrap();
+    assert!(existing.onboarding_completed);
+}
+
+#[test]
+fn test_import_merges_templates_and_keeps_security_settings() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("config.json");
+
+    let mut source = Settings::default();
+    source.copy_templates.truncate(1);
+    source.copy_templates[0].template = "{login}".to_string();
+    source.lock_hotkey = "Ctrl+Q".to_string();
+    source.deep_links_enabled = true;
+    source.export_config(&path).unwrap();
+
+    let mut target = Settings::default();
+    let template_count = target.copy_templates.len();
+    target.import_config(&path, false).unwrap();
+
+    assert_eq!(target.copy_templates.len(), template_count);
+    assert_eq!(target.copy_templates[0].template, "{login}");
+    assert_eq!(target.lock_hotkey, Settings::default().lock_hotkey);
+    assert!(!target.deep_links_enabled);
+
+    target.import_config(&path, true).unwrap();
+    assert_eq!(target.lock_hotkey, "Ctrl+Q");
+    assert!(target.deep_links_enabled);
+}
@@ -0,0 +1,758 @@
+use mdp_manager::crypto::*;
+use mdp_manager::models::{Entry, EntryKind, Vault, VaultFile, VAULT_FILE_VERSION};
+use mdp_manager::storage::*;
+use mdp_manager::vault_storage::{resolve_vault_path, MemoryStorage, VaultStorage};
+use tempfile::tempdir;
+
+#[test]
+fn test_verify_master_password() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+
+    save_vault(&Vault::new(), &path, "correct_password").unwrap();
+
+    assert!(verify_master_password(&path, "correct_password").unwrap());
+    assert!(!verify_master_password(&path, "wrong_password").unwrap());
+}
+
+#[test]
+fn test_verify_master_password_missing_file() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("missing.json");
+
+    assert!(verify_master_password(&path, "password").is_err());
+}
+
+#[test]
+fn test_storage_errors_are_typed() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+
+    let missing = load_vault(&path, "password").unwrap_err();
+    assert!(matches!(missing, StorageError::Io(ref e) if e.kind() == std::io::ErrorKind::NotFound));
+
+    save_vault(&Vault::new(), &path, "correct_password").unwrap();
+    assert!(load_vault(&path, "wrong_password").unwrap_err().is_wrong_password());
+
+    std::fs::write(&path, "pas du json").unwrap();
+    assert!(matches!(load_vault(&path, "correct_password"), Err(StorageError::Serialization(_))));
+}
+
+#[test]
+fn test_decoy_vault_opens_only_its_own_entries() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+
+    let mut real = Vault::new();
+    real.add_entry(Entry::new("Banque".into(), "alice".into(), "secret".into()));
+    let mut decoy = Vault::new();
+    decoy.add_entry(Entry::new("Forum".into(), "bob".into(), "leurre".into()));
+
+    create_vault_file(&real, &path, "real_password").unwrap();
+    save_vault_slot(&decoy, &path, "decoy_password", VaultSlot::Secondary).unwrap();
+
+    let (opened, slot) = load_vault_slot(&path, "real_password").unwrap();
+    assert_eq!(slot, VaultSlot::Primary);
+    assert_eq!(opened.entries.len(), 1);
+    assert_eq!(opened.entries[0].name, "Banque");
+
+    let (opened, slot) = load_vault_slot(&path, "decoy_password").unwrap();
+    assert_eq!(slot, VaultSlot::Secondary);
+    assert_eq!(opened.entries.len(), 1);
+    assert_eq!(opened.entries[0].name, "Forum");
+
+    assert!(load_vault(&path, "other_password").is_err());
+}
+
+#[test]
+fn test_saving_primary_preserves_decoy() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+
+    create_vault_file(&Vault::new(), &path, "real_password").unwrap();
+    save_vault_slot(&Vault::new(), &path, "decoy_password", VaultSlot::Secondary).unwrap();
+    save_vault(&Vault::new(), &path, "real_password").unwrap();
+
+    let (_, slot) = load_vault_slot(&path, "decoy_password").unwrap();
+    assert_eq!(slot, VaultSlot::Secondary);
+}
+
+fn write_legacy_v1_file(path: &std::path::Path, vault: &Vault, password: &str) {
+    let salt = generate_salt();
+    let nonce = generate_nonce();
+    let key = derive_key(password, &salt, &CryptoParams::default()).unwrap();
+    let plaintext = serde_json::to_string(vault).unwrap();
+    let ciphertext = encrypt(plaintext.as_bytes(), &key, &nonce).unwrap();
+
+    let json = serde_json::json!({
+        "version": 1,
+        "kdf": "argon2id",
+        "salt": encode_base64(&salt),
+        "nonce": encode_base64(&nonce),
+        "ciphertext": encode_base64(&ciphertext),
+    });
+    std::fs::write(path, serde_json::to_string_pretty(&json).unwrap()).unwrap();
+}
+
+#[test]
+fn test_legacy_v1_file_opens_and_migrates() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("legacy.json");
+
+    let mut vault = Vault::new();
+    vault.add_entry(Entry::new("Ancien".into(), "user".into(), "pass".into()));
+    write_legacy_v1_file(&path, &vault, "legacy_password");
+
+    let loaded = open_vault_file(&path, "legacy_password").unwrap();
+    assert_eq!(loaded.migrated_from, Some(1));
+    assert_eq!(loaded.vault.entries[0].name, "Ancien");
+
+    save_vault(&loaded.vault, &path, "legacy_password").unwrap();
+
+    let raw: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(raw["version"], VAULT_FILE_VERSION);
+    assert!(raw.get("kdf_params").is_some());
+
+    let reloaded = open_vault_file(&path, "legacy_password").unwrap();
+    assert_eq!(reloaded.migrated_from, None);
+    assert_eq!(reloaded.vault.entries.len(), 1);
+}
+
+#[test]
+fn test_decomposed_password_from_before_nfc_still_opens() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+    let decomposed = "mot de passe e\u{301}te\u{301}";
+    let composed = "mot de passe \u{e9}t\u{e9}";
+
+    // Coffre dérivé des octets saisis, comme avant la normalisation NFC
+    let mut vault = Vault::new();
+    vault.add_entry(Entry::new("macOS".into(), "user".into(), "pass".into()));
+    let salt = generate_salt();
+    let nonce = generate_nonce();
+    let key = derive_key_unnormalized(decomposed, &salt, &CryptoParams::default()).unwrap();
+    let plaintext = serde_json::to_string(&vault).unwrap();
+    let ciphertext = encrypt(plaintext.as_bytes(), &key, &nonce).unwrap();
+    let json = serde_json::json!({
+        "version": VAULT_FILE_VERSION,
+        "kdf": "argon2id",
+        "kdf_params": CryptoParams::default(),
+        "salt": encode_base64(&salt),
+        "nonce": encode_base64(&nonce),
+        "ciphertext": encode_base64(&ciphertext),
+    });
+    std::fs::write(&path, serde_json::to_string_pretty(&json).unwrap()).unwrap();
+
+    let loaded = open_vault_file(&path, decomposed).unwrap();
+    assert_eq!(loaded.vault.entries[0].name, "macOS");
+
+    // Ré-enregistré sous NFC: les deux formes ouvrent désormais le coffre
+    assert_eq!(open_vault_file(&path, composed).unwrap().vault.entries.len(), 1);
+    assert_eq!(open_vault_file(&path, decomposed).unwrap().vault.entries.len(), 1);
+}
+
+#[cfg(feature = "age")]
+#[test]
+fn test_age_export_import_roundtrip() {
+    use age::secrecy::ExposeSecret;
+
+    let dir = tempdir().unwrap();
+    let export_path = dir.path().join("vault.age");
+    let identity_path = dir.path().join("identity.txt");
+
+    let identity = age::x25519::Identity::generate();
+    let recipient = identity.to_public().to_string();
+    std::fs::write(&identity_path, identity.to_string().expose_secret()).unwrap();
+
+    let mut vault = Vault::new();
+    vault.add_entry(Entry::new("Serveur".into(), "root".into(), "hunter2".into()));
+
+    export_age(&vault, &export_path, &[recipient.as_str()]).unwrap();
+    let imported = import_age(&export_path, &identity_path).unwrap();
+
+    assert_eq!(imported.entries.len(), 1);
+    assert_eq!(imported.entries[0].password, "hunter2");
+}
+
+#[cfg(feature = "age")]
+#[test]
+fn test_age_export_to_two_recipients() {
+    use age::secrecy::ExposeSecret;
+
+    let dir = tempdir().unwrap();
+    let export_path = dir.path().join("vault.age");
+
+    let alice = age::x25519::Identity::generate();
+    let bob = age::x25519::Identity::generate();
+    let recipients = [alice.to_public().to_string(), bob.to_public().to_string()];
+
+    let mut vault = Vault::new();
+    vault.add_entry(Entry::new("Partagé".into(), "equipe".into(), "hunter2".into()));
+    export_age(&vault, &export_path, &[recipients[0].as_str(), recipients[1].as_str()]).unwrap();
+
+    for (name, identity) in [("alice.txt", &alice), ("bob.txt", &bob)] {
+        let identity_path = dir.path().join(name);
+        std::fs::write(&identity_path, identity.to_string().expose_secret()).unwrap();
+        let imported = import_age(&export_path, &identity_path).unwrap();
+        assert_eq!(imported.entries[0].password, "hunter2");
+    }
+
+    let err = export_age(&vault, &export_path, &[recipients[0].as_str(), "age1pasunecle"]).unwrap_err();
+    assert!(err.to_string().contains("n°2"));
+}
+
+#[test]
+fn test_save_with_session_keeps_password() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+
+    create_vault_file(&Vault::new(), &path, "session_password").unwrap();
+
+    let mut loaded = open_vault_file(&path, "session_password").unwrap();
+    loaded.vault.add_entry(Entry::new("Mail".into(), "me".into(), "pw".into()));
+    save_vault_session(&loaded.vault, &path, &loaded.session).unwrap();
+
+    let reloaded = load_vault(&path, "session_password").unwrap();
+    assert_eq!(reloaded.entries.len(), 1);
+    assert_eq!(reloaded.entries[0].name, "Mail");
+}
+
+#[test]
+fn test_parse_csv_from_memory() {
+    let data = b"name,login,password,url,notes,tags\n\
+Mail,alice,secret,https://mail.example,,perso; web\n\
+Sans login,,x,,,\n";
+
+    let entries = parse_csv(&data[..]).unwrap();
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].name, "Mail");
+    assert_eq!(entries[0].urls, vec!["https://mail.example"]);
+    assert_eq!(entries[0].notes, None);
+    assert_eq!(entries[0].tags, vec!["perso", "web"]);
+}
+
+#[test]
+fn test_import_encrypted_backup_with_its_own_password() {
+    let dir = tempdir().unwrap();
+    let vault_path = dir.path().join("vault.json");
+    let backup_path = dir.path().join("backup.mdpx");
+
+    let mut backup = Vault::new();
+    backup.add_entry(Entry::new("Sauvegarde".into(), "old".into(), "pw".into()));
+    export_encrypted(&backup, &backup_path, "backup_password").unwrap();
+
+    let mut current = Vault::new();
+    current.add_entry(Entry::new("Actuel".into(), "me".into(), "pw".into()));
+    create_vault_file(&current, &vault_path, "vault_password").unwrap();
+
+    assert!(import_encrypted(&backup_path, "vault_password").is_err());
+
+    let imported = import_encrypted(&backup_path, "backup_password").unwrap();
+    assert_eq!(current.merge_entries(imported.clone()), 1);
+    assert_eq!(current.merge_entries(imported), 0);
+
+    let names: Vec<&str> = current.entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["Actuel", "Sauvegarde"]);
+}
+
+#[test]
+fn test_export_csv_with_column_subset() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("export.csv");
+
+    let mut vault = Vault::new();
+    let mut entry = Entry::new("Mail".into(), "alice".into(), "secret".into());
+    entry.urls = vec!["https://mail.example".into()];
+    let id = entry.id;
+    vault.add_entry(entry);
+
+    let options = CsvExportOptions {
+        include_id: true,
+        include_login: false,
+        include_password: false,
+        include_notes: false,
+        include_tags: false,
+        ..Default::default()
+    };
+    export_csv(&vault, &path, &options).unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(lines, vec!["id,name,url".to_string(), format!("{},Mail,https://mail.example", id)]);
+    assert!(!content.contains("secret"));
+}
+
+#[test]
+fn test_reencrypt_rotates_salt_and_nonce() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+
+    let mut vault = Vault::new();
+    vault.add_entry(Entry::new("Mail".into(), "alice".into(), "secret".into()));
+    create_vault_file(&vault, &path, "master_password").unwrap();
+
+    let read = |path: &std::path::Path| -> VaultFile {
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    };
+    let before = read(&path);
+
+    assert!(reencrypt_vault(&vault, &path, "wrong_password", VaultSlot::Primary).is_err());
+    let session = reencrypt_vault(&vault, &path, "master_password", VaultSlot::Primary).unwrap();
+    assert_eq!(session.slot(), VaultSlot::Primary);
+
+    let after = read(&path);
+    assert_ne!(before.salt, after.salt);
+    assert_ne!(before.nonce, after.nonce);
+
+    let (reopened, _) = load_vault_slot(&path, "master_password").unwrap();
+    assert_eq!(reopened.entries.len(), 1);
+    assert_eq!(reopened.entries[0].password, "secret");
+}
+
+#[test]
+fn test_open_vault_with_reconstructed_key_shares() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+
+    let mut vault = Vault::new();
+    vault.add_entry(Entry::new("Coffre-fort".into(), "alice".into(), "secret".into()));
+    let session = create_vault_file(&vault, &path, "master_password").unwrap();
+
+    let shares = session.key_shares(3, 2);
+    let key = combine_key(&shares[1..]).unwrap();
+
+    let loaded = open_vault_file_with_key(&path, &key).unwrap();
+    assert_eq!(loaded.slot, VaultSlot::Primary);
+    assert_eq!(loaded.vault.entries[0].name, "Coffre-fort");
+
+    assert!(open_vault_file_with_key(&path, &random_bytes(32)).is_err());
+}
+
+#[test]
+fn test_derive_time_is_measured_only_with_password() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+
+    let session = create_vault_file(&Vault::new(), &path, "master_password").unwrap();
+
+    let loaded = open_vault_file(&path, "master_password").unwrap();
+    assert!(loaded.derive_time.is_some());
+
+    let key = combine_key(&session.key_shares(2, 2)).unwrap();
+    let loaded = open_vault_file_with_key(&path, &key).unwrap();
+    assert!(loaded.derive_time.is_none());
+}
+
+#[test]
+fn test_manifest_detects_altered_vault_file() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+    let manifest_path = dir.path().join("vault.manifest.json");
+
+    let mut vault = Vault::new();
+    vault.add_entry(Entry::new("Banque".into(), "alice".into(), "secret".into()));
+    create_vault_file(&vault, &path, "password123").unwrap();
+    write_manifest(&vault, &path, &manifest_path).unwrap();
+
+    assert!(verify_manifest(&path, &manifest_path).unwrap());
+
+    // Un seul octet modifié suffit
+    let mut contents = std::fs::read(&path).unwrap();
+    let last = contents.len() - 2;
+    contents[last] ^= 0x01;
+    std::fs::write(&path, contents).unwrap();
+
+    assert!(!verify_manifest(&path, &manifest_path).unwrap());
+}
+
+#[test]
+fn test_signed_manifest_requires_trusted_key() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+    let manifest_path = dir.path().join("vault.manifest.json");
+
+    let vault = Vault::new();
+    create_vault_file(&vault, &path, "password123").unwrap();
+
+    let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+    let other = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+    write_manifest_signed(&vault, &path, &manifest_path, Some(&key)).unwrap();
+
+    assert!(verify_manifest(&path, &manifest_path).unwrap());
+    assert!(verify_manifest_signed(&path, &manifest_path, &key.verifying_key()).unwrap());
+    assert!(!verify_manifest_signed(&path, &manifest_path, &other.verifying_key()).unwrap());
+}
+
+#[test]
+fn test_import_csv_with_utf8_bom() {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/excel_bom.csv");
+
+    let entries = import_csv(&path).unwrap();
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].name, "Banque");
+    assert_eq!(entries[0].notes.as_deref(), Some("Compte courant"));
+}
+
+#[test]
+fn test_import_csv_latin1_fallback() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("latin1.csv");
+    // "Société" encodé en Latin-1 (é = 0xE9)
+    std::fs::write(&path, b"name,login,password\nSoci\xe9t\xe9,alice,secret\n").unwrap();
+
+    let entries = import_csv(&path).unwrap();
+
+    assert_eq!(entries[0].name, "Société");
+}
+
+#[test]
+fn test_decode_csv_rejects_bytes_undefined_in_windows_1252() {
+    // 0x81 et 0x9D n'ont pas de caractère en Windows-1252
+    assert!(decode_csv_bytes(b"name\nSoci\xe9t\xe9\x81\x9d\n").is_err());
+    assert_eq!(decode_csv_bytes(b"name\r\nCaf\xe9\t\n").unwrap(), "name\r\nCafé\t\n");
+}
+
+#[test]
+fn test_memory_storage_roundtrip() {
+    let storage = MemoryStorage::new();
+    assert!(load_vault_from(&storage, "password123").is_err());
+
+    let mut vault = Vault::new();
+    vault.add_entry(Entry::new("Banque".into(), "alice".into(), "secret".into()));
+    save_vault_to(&vault, &storage, "password123").unwrap();
+
+    // Seul le chiffré transite par le support
+    let raw = String::from_utf8(storage.read_bytes().unwrap()).unwrap();
+    assert!(!raw.contains("secret"));
+
+    let loaded = load_vault_from(&storage, "password123").unwrap();
+    assert_eq!(loaded.entries.len(), 1);
+    assert_eq!(loaded.entries[0].password, "secret");
+    assert!(load_vault_from(&storage, "wrong_password").is_err());
+}
+
+#[test]
+fn test_encrypted_csv_export_roundtrip() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("export.csv.enc");
+
+    let mut vault = Vault::new();
+    let mut entry = Entry::new("Banque".into(), "alice".into(), "s3cret".into());
+    entry.tags = vec!["finances".into()];
+    entry.urls = vec!["https://banque.example".into(), "https://m.banque.example".into()];
+    vault.add_entry(entry);
+    let mut note = Entry::new("Code du coffre-fort".into(), String::new(), String::new());
+    note.kind = EntryKind::SecureNote;
+    note.notes = Some("4512".into());
+    vault.add_entry(note);
+
+    export_csv_encrypted(&vault, &path, "one-time-pass").unwrap();
+
+    let raw = std::fs::read_to_string(&path).unwrap();
+    assert!(!raw.contains("s3cret"));
+    assert!(!raw.contains("Banque"));
+
+    let entries = decrypt_csv_export(&path, "one-time-pass").unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].name, "Banque");
+    assert_eq!(entries[0].login, "alice");
+    assert_eq!(entries[0].password, "s3cret");
+    assert_eq!(entries[0].urls.len(), 2);
+    assert_eq!(entries[0].tags, vec!["finances"]);
+    // Les notes sécurisées survivent à l'aller-retour
+    assert!(entries[1].is_secure_note());
+    assert_eq!(entries[1].notes.as_deref(), Some("4512"));
+
+    assert!(decrypt_csv_export(&path, "wrong-password").is_err());
+}
+
+#[test]
+fn test_vault_metadata_readable_without_password_when_opted_in() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+
+    let mut vault = Vault::new();
+    vault.add_entry(Entry::new("Banque".into(), "alice".into(), "secret".into()));
+    vault.add_entry(Entry::new("Mail".into(), "alice".into(), "secret2".into()));
+
+    // Désactivé par défaut: rien n'est publié
+    save_vault(&vault, &path, "password123").unwrap();
+    assert!(load_vault_metadata(&path).is_err());
+
+    vault.settings.expose_metadata = true;
+    save_vault(&vault, &path, "password123").unwrap();
+
+    let meta = load_vault_metadata(&path).unwrap();
+    assert_eq!(meta.entry_count, 2);
+    assert_eq!(meta.modified_at, vault.modified_at);
+}
+
+#[test]
+fn test_decoy_save_never_republishes_primary_metadata() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+
+    let mut vault = Vault::new();
+    vault.add_entry(Entry::new("Banque".into(), "alice".into(), "secret".into()));
+    vault.settings.expose_metadata = true;
+    save_vault(&vault, &path, "password123").unwrap();
+    assert!(load_vault_metadata(&path).is_ok());
+
+    save_vault_slot(&Vault::new(), &path, "decoy-password", VaultSlot::Secondary).unwrap();
+    assert!(load_vault_metadata(&path).is_err());
+}
+
+#[test]
+fn test_load_vault_from_directory_is_invalid_path() {
+    let dir = tempdir().unwrap();
+
+    let err = load_vault(dir.path(), "password").unwrap_err();
+    assert!(matches!(err, StorageError::InvalidPath(..)));
+    assert!(err.to_string().contains("dossier"));
+    assert!(matches!(resolve_vault_path(dir.path()), Err(StorageError::InvalidPath(..))));
+
+    // Un chemin encore inexistant reste valide: le coffre sera créé
+    let missing = dir.path().join("nouveau.json");
+    assert_eq!(resolve_vault_path(&missing).unwrap(), missing);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlink_loop_is_invalid_path() {
+    let dir = tempdir().unwrap();
+    let a = dir.path().join("a.json");
+    let b = dir.path().join("b.json");
+    std::os::unix::fs::symlink(&b, &a).unwrap();
+    std::os::unix::fs::symlink(&a, &b).unwrap();
+
+    assert!(matches!(resolve_vault_path(&a), Err(StorageError::InvalidPath(..))));
+    assert!(save_vault(&Vault::new(), &a, "password").is_err());
+}
+
+#[test]
+fn test_sealed_notes_roundtrip() {
+    let mut entry = Entry::new("Banque".into(), "alice".into(), "secret".into());
+    entry.notes = Some("Codes de récupération: 1111-2222".into());
+
+    seal_notes(&mut entry, "second_password").unwrap();
+    assert!(entry.notes.is_none());
+    assert!(entry.sealed_notes.is_some());
+
+    // Les notes protégées survivent à la sérialisation du coffre sans apparaître en clair
+    let json = serde_json::to_string(&entry).unwrap();
+    assert!(!json.contains("1111-2222"));
+    let reloaded: Entry = serde_json::from_str(&json).unwrap();
+
+    assert!(open_sealed_notes(&reloaded, "wrong_password").is_err());
+    assert_eq!(open_sealed_notes(&reloaded, "second_password").unwrap(), "Codes de récupération: 1111-2222");
+
+    let mut reloaded = reloaded;
+    remove_notes_seal(&mut reloaded, "second_password").unwrap();
+    assert_eq!(reloaded.notes.as_deref(), Some("Codes de récupération: 1111-2222"));
+    assert!(reloaded.sealed_notes.is_none());
+}
+
+#[test]
+fn test_backup_vault_file_copies_before_replace() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+    save_vault(&Vault::new(), &path, "password").unwrap();
+
+    let backup = backup_vault_file(&path).unwrap();
+
+    assert_ne!(backup, path);
+    assert!(backup.to_string_lossy().ends_with(".bak"));
+    assert_eq!(std::fs::read(&backup).unwrap(), std::fs::read(&path).unwrap());
+    assert!(load_vault(&backup, "password").is_ok());
+}
+
+#[test]
+fn test_timelocked_export_refused_before_date() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("urgence.mdpu");
+    let mut vault = Vault::new();
+    vault.add_entry(Entry::new("Banque".into(), "alice".into(), "secret".into()));
+    let not_before = chrono::Utc::now() + chrono::Duration::days(30);
+
+    export_timelocked(&vault, &path, "mot de passe du notaire", not_before).unwrap();
+
+    let early = import_timelocked(&path, "mot de passe du notaire").unwrap_err();
+    assert!(matches!(early, StorageError::NotYetAvailable(date) if date == not_before));
+
+    let later = not_before + chrono::Duration::seconds(1);
+    let opened = import_timelocked_at(&path, "mot de passe du notaire", later).unwrap();
+    assert_eq!(opened.entries[0].name, "Banque");
+    assert!(import_timelocked_at(&path, "autre", later).unwrap_err().is_wrong_password());
+}
+
+#[test]
+fn test_timelocked_export_date_cannot_be_edited() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("urgence.mdpu");
+    let not_before = chrono::Utc::now() + chrono::Duration::days(30);
+    export_timelocked(&Vault::new(), &path, "notaire", not_before).unwrap();
+
+    // Avancer la date dans le fichier ne suffit pas: elle fait partie de la clé
+    let mut json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    json["not_before"] = serde_json::json!("2000-01-01T00:00:00Z");
+    std::fs::write(&path, json.to_string()).unwrap();
+
+    assert!(import_timelocked(&path, "notaire").unwrap_err().is_wrong_password());
+}
+
+/// Support dont l'écriture échoue à la demande, comme un disque plein
+struct FullDisk {
+    inner: MemoryStorage,
+    full: std::cell::Cell<bool>,
+}
+
+impl VaultStorage for FullDisk {
+    fn read_bytes(&self) -> std::io::Result<Vec<u8>> {
+        self.inner.read_bytes()
+    }
+
+    fn write_bytes(&self, data: &[u8]) -> std::io::Result<()> {
+        if self.full.get() {
+            return Err(std::io::Error::other("No space left on device"));
+        }
+        self.inner.write_bytes(data)
+    }
+}
+
+#[test]
+fn test_failed_save_is_reported_and_keeps_previous_file() {
+    let storage = FullDisk {
+        inner: MemoryStorage::new(),
+        full: std::cell::Cell::new(false),
+    };
+    let mut vault = Vault::new();
+    vault.add_entry(Entry::new("Banque".into(), "alice".into(), "secret".into()));
+    let session = create_vault_in(&vault, &storage, "password123", CryptoParams::default()).unwrap();
+
+    storage.full.set(true);
+    vault.add_entry(Entry::new("Courriel".into(), "bob".into(), "secret".into()));
+    let err = save_vault_session_to(&vault, &storage, &session).unwrap_err();
+    assert!(matches!(err, StorageError::Io(_)));
+
+    // L'ancien contenu reste lisible; la nouvelle entrée n'existe qu'en mémoire
+    let loaded = load_vault_from(&storage, "password123").unwrap();
+    assert_eq!(loaded.entries.len(), 1);
+
+    storage.full.set(false);
+    save_vault_session_to(&vault, &storage, &session).unwrap();
+    assert_eq!(load_vault_from(&storage, "password123").unwrap().entries.len(), 2);
+}
+
+#[test]
+fn test_unsaved_snapshot_survives_lock_and_reopens_with_same_vault() {
+    let storage = FullDisk {
+        inner: MemoryStorage::new(),
+        full: std::cell::Cell::new(false),
+    };
+    let mut vault = Vault::new();
+    let session = create_vault_in(&vault, &storage, "password123", CryptoParams::default()).unwrap();
+    save_vault_slot_to(&Vault::new(), &storage, "decoy-password", VaultSlot::Secondary).unwrap();
+
+    // Disque plein au verrouillage: seul l'instantané chiffré garde la nouvelle entrée
+    storage.full.set(true);
+    vault.add_entry(Entry::new("Courriel".into(), "bob".into(), "secret".into()));
+    let snapshot = seal_snapshot(&vault, &session).unwrap();
+    drop(session);
+
+    let decoy = open_vault_from(&storage, "decoy-password", |_| {}).unwrap();
+    assert!(open_snapshot(&snapshot, &decoy.session).is_err());
+
+    let reopened = open_vault_from(&storage, "password123", |_| {}).unwrap();
+    assert_eq!(reopened.vault.entries.len(), 0);
+    assert_eq!(open_snapshot(&snapshot, &reopened.session).unwrap().entries.len(), 1);
+}
+
+fn json_export_vault() -> Vault {
+    let mut vault = Vault::new();
+    let mut entry = Entry::new("Banque".into(), "alice".into(), "s3cret-unique".into());
+    entry.tags = vec!["finances".into(), "perso".into()];
+    entry.urls = vec!["https://banque.example".into(), "https://app.banque.example".into()];
+    entry.totp = Some(mdp_manager::totp::parse_totp("JBSWY3DPEHPK3PXP").unwrap());
+    vault.add_entry(entry);
+    vault
+}
+
+#[test]
+fn test_json_export_roundtrip_with_secrets() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("export.json");
+    let vault = json_export_vault();
+
+    export_json(&vault, &path, true).unwrap();
+    let entries = import_json(&path).unwrap();
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].id, vault.entries[0].id);
+    assert_eq!(entries[0].password, "s3cret-unique");
+    assert_eq!(entries[0].tags, vec!["finances", "perso"]);
+    assert_eq!(entries[0].urls.len(), 2);
+    assert_eq!(entries[0].totp.as_ref().unwrap().secret, "JBSWY3DPEHPK3PXP");
+}
+
+#[test]
+fn test_json_export_redacts_secrets() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("export.json");
+
+    export_json(&json_export_vault(), &path, false).unwrap();
+
+    let raw = std::fs::read_to_string(&path).unwrap();
+    assert!(!raw.contains("s3cret-unique"));
+    assert!(!raw.contains("JBSWY3DPEHPK3PXP"));
+
+    assert!(raw.contains("finances"));
+
+    // Réimporté, il écraserait les vrais mots de passe par des vides
+    assert!(import_json(&path).is_err());
+}
+
+#[test]
+fn test_verify_backup_detects_corruption() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("sauvegarde.mdpx");
+    let mut vault = Vault::new();
+    vault.add_entry(Entry::new("Banque".into(), "alice".into(), "secret".into()));
+    vault.add_entry(Entry::new("Courriel".into(), "alice".into(), "secret".into()));
+
+    export_encrypted(&vault, &path, "backup_password").unwrap();
+    assert_eq!(verify_backup(&path, "backup_password", 2).unwrap(), 2);
+    assert!(verify_backup(&path, "backup_password", 3).is_err());
+
+    // Un octet du chiffré modifié: l'authentification AES-GCM échoue
+    let mut file: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let mut ciphertext = decode_base64(file["ciphertext"].as_str().unwrap()).unwrap();
+    ciphertext[0] ^= 0xFF;
+    file["ciphertext"] = serde_json::json!(encode_base64(&ciphertext));
+    std::fs::write(&path, file.to_string()).unwrap();
+
+    assert!(verify_backup(&path, "backup_password", 2).is_err());
+}
+
+#[test]
+fn test_check_breaches_in_local_list() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("pwned.txt");
+    // SHA-1 de "password", en minuscules comme certaines listes
+    let list = "0000000000000000000000000000000000000000:1\n5baa61e4c9b93f3f0682250b6cf8331b7ee68fd8:42\n";
+    std::fs::write(&path, list).unwrap();
+
+    let mut vault = Vault::new();
+    let leaked = Entry::new("Forum".into(), "alice".into(), "password".into());
+    let clean = Entry::new("Banque".into(), "alice".into(), "k7#Vq9!mZ2@x".into());
+    let (leaked_id, clean_id) = (leaked.id, clean.id);
+    vault.add_entry(leaked);
+    vault.add_entry(clean);
+
+    assert_eq!(check_breaches_in_file(&mut vault, &path).unwrap(), 1);
+    assert_eq!(vault.get_entry(leaked_id).unwrap().breach_status.as_ref().unwrap().count, 42);
+    assert!(!vault.get_entry(clean_id).unwrap().breach_status.as_ref().unwrap().is_breached());
+    assert_eq!(vault.breached_names(), vec!["Forum".to_string()]);
+}
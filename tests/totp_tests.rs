@@ -0,0 +1,88 @@
+use mdp_manager::models::Entry;
+use mdp_manager::totp::*;
+
+#[test]
+fn test_parse_bare_secret() {
+    let config = parse_totp("jbsw y3dp ehpk 3pxp").unwrap();
+
+    assert_eq!(config.secret, "JBSWY3DPEHPK3PXP");
+    assert_eq!(config.digits, 6);
+    assert_eq!(config.period, 30);
+}
+
+#[test]
+fn test_parse_otpauth_uri_with_custom_period() {
+    let config = parse_totp(
+        "otpauth://totp/Example:alice@example.com?secret=JBSWY3DPEHPK3PXP&issuer=Example&digits=8&period=60",
+    )
+    .unwrap();
+
+    assert_eq!(config.secret, "JBSWY3DPEHPK3PXP");
+    assert_eq!(config.digits, 8);
+    assert_eq!(config.period, 60);
+}
+
+#[test]
+fn test_reject_malformed_totp() {
+    assert!(parse_totp("not base32 !").is_err());
+    assert!(parse_totp("otpauth://hotp/Example?secret=JBSWY3DPEHPK3PXP").is_err());
+    assert!(parse_totp("otpauth://totp/Example?issuer=Example").is_err());
+    assert!(parse_totp("otpauth://totp/Example?secret=JBSWY3DPEHPK3PXP&digits=12").is_err());
+}
+
+#[test]
+fn test_reject_out_of_range_stored_config() {
+    let zero_period = r#"{"secret":"JBSWY3DPEHPK3PXP","digits":6,"period":0}"#;
+    let too_many_digits = r#"{"secret":"JBSWY3DPEHPK3PXP","digits":10,"period":30}"#;
+    assert!(serde_json::from_str::<TotpConfig>(zero_period).is_err());
+    assert!(serde_json::from_str::<TotpConfig>(too_many_digits).is_err());
+    assert!(parse_totp("otpauth://totp/Example?secret=JBSWY3DPEHPK3PXP&period=0").is_err());
+
+    // Construite à la main, la configuration ne fait pas paniquer le calcul
+    let config = TotpConfig {
+        secret: "JBSWY3DPEHPK3PXP".to_string(),
+        digits: 10,
+        period: 0,
+    };
+    assert_eq!(config.code_at(59), None);
+    assert_eq!(config.remaining_secs(59), 0);
+}
+
+#[test]
+fn test_decode_base32() {
+    assert_eq!(decode_base32("JBSWY3DPEHPK3PXP").unwrap(), b"Hello!\xde\xad\xbe\xef");
+}
+
+// Vecteurs de la RFC 6238 (SHA1, secret ASCII "12345678901234567890")
+fn rfc_config() -> TotpConfig {
+    TotpConfig {
+        secret: "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ".to_string(),
+        digits: 8,
+        period: 30,
+    }
+}
+
+#[test]
+fn test_totp_code_matches_rfc_vectors() {
+    let config = rfc_config();
+
+    assert_eq!(config.code_at(59).as_deref(), Some("94287082"));
+    assert_eq!(config.code_at(1111111109).as_deref(), Some("07081804"));
+    assert_eq!(config.code_at(2000000000).as_deref(), Some("69279037"));
+    assert_eq!(config.remaining_secs(59), 1);
+}
+
+#[test]
+fn test_totp_window_covers_adjacent_periods() {
+    let mut entry = Entry::new("2FA".into(), "alice".into(), "secret".into());
+    assert!(entry.totp_window(1).is_empty());
+
+    entry.totp = Some(rfc_config());
+    let window = entry.totp_window_at(59, 1);
+
+    let offsets: Vec<i64> = window.iter().map(|(_, offset)| *offset).collect();
+    assert_eq!(offsets, vec![-1, 0, 1]);
+    assert_eq!(window[1].0, "94287082");
+    assert_eq!(window[0].0, rfc_config().code_at(29).unwrap());
+    assert_eq!(window[2].0, rfc_config().code_at(89).unwrap());
+}